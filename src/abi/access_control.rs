@@ -0,0 +1,136 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::signing::keccak256;
+use web3::types::{Address, Bytes, H256, Log};
+use crate::abi::{ContractAbi, Error, Result};
+
+// OpenZeppelin AccessControl
+pub const ACCESS_CONTROL_ABI_JSON: &str = "[{\"inputs\":[],\"name\":\"DEFAULT_ADMIN_ROLE\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"hasRole\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"}],\"name\":\"getRoleAdmin\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"grantRole\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"revokeRole\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"renounceRole\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"sender\",\"type\":\"address\"}],\"name\":\"RoleGranted\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"sender\",\"type\":\"address\"}],\"name\":\"RoleRevoked\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"bytes32\",\"name\":\"role\",\"type\":\"bytes32\"},{\"indexed\":true,\"internalType\":\"bytes32\",\"name\":\"previousAdminRole\",\"type\":\"bytes32\"},{\"indexed\":true,\"internalType\":\"bytes32\",\"name\":\"newAdminRole\",\"type\":\"bytes32\"}],\"name\":\"RoleAdminChanged\",\"type\":\"event\"}]";
+
+// 常用角色名称, 链上的角色为 keccak256(名称), DEFAULT_ADMIN_ROLE 为 0x00
+pub const DEFAULT_ADMIN_ROLE: &str = "DEFAULT_ADMIN_ROLE";
+pub const MINTER_ROLE: &str = "MINTER_ROLE";
+pub const BURNER_ROLE: &str = "BURNER_ROLE";
+pub const PAUSER_ROLE: &str = "PAUSER_ROLE";
+pub const UPGRADER_ROLE: &str = "UPGRADER_ROLE";
+
+pub fn role(name: &str) -> H256 {
+    if name == DEFAULT_ADMIN_ROLE {
+        return H256::zero();
+    }
+    H256::from(keccak256(name.as_bytes()))
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoleChangedEvent {
+    pub role: H256,
+    pub account: Address,
+    pub sender: Address,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum RoleEvent {
+    Granted(RoleChangedEvent),
+    Revoked(RoleChangedEvent),
+}
+
+pub trait AccessControlContractAbi: ContractAbi {
+    fn has_role(&self, role: H256, account: Address) -> Result<Bytes> {
+        self.method("hasRole", &(role, account).into_tokens())
+    }
+
+    fn decode_has_role(&self, data: Bytes) -> Result<bool> {
+        let tokens = self.decode_method("hasRole", data)?;
+        Ok(bool::from_tokens(tokens)?)
+    }
+
+    fn get_role_admin(&self, role: H256) -> Result<Bytes> {
+        self.method("getRoleAdmin", &role.into_tokens())
+    }
+
+    fn decode_get_role_admin(&self, data: Bytes) -> Result<H256> {
+        let tokens = self.decode_method("getRoleAdmin", data)?;
+        Ok(H256::from_tokens(tokens)?)
+    }
+
+    fn grant_role(&self, role: H256, account: Address) -> Result<Bytes> {
+        self.method("grantRole", &(role, account).into_tokens())
+    }
+
+    fn revoke_role(&self, role: H256, account: Address) -> Result<Bytes> {
+        self.method("revokeRole", &(role, account).into_tokens())
+    }
+
+    // account 必须是调用者自己
+    fn renounce_role(&self, role: H256, account: Address) -> Result<Bytes> {
+        self.method("renounceRole", &(role, account).into_tokens())
+    }
+
+    fn decode_role_log(&self, log: &Log) -> Result<RoleEvent> {
+        let topic = log.topics.first().ok_or(Error::ErrUnknownEvent)?;
+        let decode = |name: &str| -> Result<RoleChangedEvent> {
+            let (role, account, sender) = Detokenize::from_tokens(self.decode_event(name, log)?)?;
+            Ok(RoleChangedEvent { role, account, sender })
+        };
+        if *topic == self.event_topic("RoleGranted")? {
+            Ok(RoleEvent::Granted(decode("RoleGranted")?))
+        } else if *topic == self.event_topic("RoleRevoked")? {
+            Ok(RoleEvent::Revoked(decode("RoleRevoked")?))
+        } else {
+            Err(Error::ErrUnknownEvent)
+        }
+    }
+}
+
+pub struct AccessControlAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl AccessControlAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(ACCESS_CONTROL_ABI_JSON.as_bytes()).expect("ACCESS_CONTROL_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for AccessControlAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for AccessControlAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl AccessControlContractAbi for AccessControlAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, H256};
+    use crate::abi::ContractAbi;
+    use crate::mock::make_log;
+    use crate::abi::access_control::{role, AccessControlAbi, AccessControlContractAbi, RoleChangedEvent, RoleEvent, DEFAULT_ADMIN_ROLE, MINTER_ROLE};
+
+    #[test]
+    fn access_control_role() {
+        assert_eq!(role(DEFAULT_ADMIN_ROLE), H256::zero());
+        assert_eq!(hex::encode(role(MINTER_ROLE)), "9f2df0fed2c77648de5860a4cc508cd0818c85b8b8a1ab4ceeef8d981c8956a6");
+
+        let abi = AccessControlAbi::new();
+        assert_eq!(hex::encode(&abi.has_role(role(MINTER_ROLE), Address::random()).unwrap().0[..4]), "91d14854");
+        assert_eq!(hex::encode(&abi.grant_role(role(MINTER_ROLE), Address::random()).unwrap().0[..4]), "2f2ff15d");
+    }
+
+    #[test]
+    fn access_control_decode_log() {
+        let abi = AccessControlAbi::new();
+        let account = Address::random();
+        let sender = Address::random();
+        let log = make_log(vec![abi.event_topic("RoleRevoked").unwrap(), role(MINTER_ROLE), H256::from(account), H256::from(sender)], vec![]);
+        assert_eq!(abi.decode_role_log(&log).unwrap(), RoleEvent::Revoked(RoleChangedEvent { role: role(MINTER_ROLE), account, sender }));
+        assert!(abi.decode_role_log(&make_log(vec![H256::random()], vec![])).is_err());
+    }
+}
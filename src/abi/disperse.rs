@@ -0,0 +1,77 @@
+use web3::ethabi::Token;
+use web3::types::{Address, Bytes, U256};
+use crate::abi::{ContractAbi, Result};
+
+// disperse.app 合约, 一笔交易向多个地址转账
+pub const DISPERSE_ABI_JSON: &str = "[{\"constant\":false,\"inputs\":[{\"name\":\"recipients\",\"type\":\"address[]\"},{\"name\":\"values\",\"type\":\"uint256[]\"}],\"name\":\"disperseEther\",\"outputs\":[],\"payable\":true,\"stateMutability\":\"payable\",\"type\":\"function\"},{\"constant\":false,\"inputs\":[{\"name\":\"token\",\"type\":\"address\"},{\"name\":\"recipients\",\"type\":\"address[]\"},{\"name\":\"values\",\"type\":\"uint256[]\"}],\"name\":\"disperseToken\",\"outputs\":[],\"payable\":false,\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"constant\":false,\"inputs\":[{\"name\":\"token\",\"type\":\"address\"},{\"name\":\"recipients\",\"type\":\"address[]\"},{\"name\":\"values\",\"type\":\"uint256[]\"}],\"name\":\"disperseTokenSimple\",\"outputs\":[],\"payable\":false,\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]";
+
+fn recipients_tokens(recipients: &[(Address, U256)]) -> (Token, Token) {
+    (
+        Token::Array(recipients.iter().map(|(to, _)| Token::Address(*to)).collect()),
+        Token::Array(recipients.iter().map(|(_, value)| Token::Uint(*value)).collect()),
+    )
+}
+
+pub trait DisperseContractAbi: ContractAbi {
+    // 交易的 value 必须等于所有金额之和
+    fn disperse_ether(&self, recipients: &[(Address, U256)]) -> Result<Bytes> {
+        let (to, values) = recipients_tokens(recipients);
+        self.method("disperseEther", &[to, values])
+    }
+
+    // 先把总额 transferFrom 到合约再逐个转出, 需要授权 disperse 合约
+    fn disperse_token(&self, token: Address, recipients: &[(Address, U256)]) -> Result<Bytes> {
+        let (to, values) = recipients_tokens(recipients);
+        self.method("disperseToken", &[Token::Address(token), to, values])
+    }
+
+    // 逐个 transferFrom, 适用于转入合约有手续费等特殊代币
+    fn disperse_token_simple(&self, token: Address, recipients: &[(Address, U256)]) -> Result<Bytes> {
+        let (to, values) = recipients_tokens(recipients);
+        self.method("disperseTokenSimple", &[Token::Address(token), to, values])
+    }
+}
+
+pub struct DisperseAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl DisperseAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(DISPERSE_ABI_JSON.as_bytes()).expect("DISPERSE_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for DisperseAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for DisperseAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl DisperseContractAbi for DisperseAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, U256};
+    use crate::abi::disperse::{DisperseAbi, DisperseContractAbi};
+
+    #[test]
+    fn disperse_abi() {
+        let abi = DisperseAbi::new();
+        let recipients = vec![(Address::random(), U256::one()), (Address::random(), U256::from(2))];
+        let data = abi.disperse_ether(&recipients).unwrap();
+        assert_eq!(hex::encode(&data.0[..4]), "e63d38ed");
+        // 2个offset + 2个(长度 + 2个元素)
+        assert_eq!(data.0.len(), 4 + 32 * 8);
+        assert_eq!(hex::encode(&abi.disperse_token(Address::random(), &recipients).unwrap().0[..4]), "c73a2d60");
+        assert_eq!(hex::encode(&abi.disperse_token_simple(Address::random(), &recipients).unwrap().0[..4]), "51ba162c");
+    }
+}
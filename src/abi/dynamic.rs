@@ -0,0 +1,127 @@
+use std::fs::File;
+use std::path::Path;
+use std::str::FromStr;
+use serde_json::Value;
+use web3::ethabi::{Contract, ParamType, Token};
+use web3::ethabi::token::{LenientTokenizer, Tokenizer};
+use web3::types::U256;
+use crate::abi::{ContractAbi, Error, Result};
+
+// 从任意ABI JSON加载的合约ABI
+pub struct DynamicAbi {
+    abi: Contract
+}
+
+impl DynamicAbi {
+    pub fn new(abi: Contract) -> Self {
+        Self { abi }
+    }
+
+    // 支持纯ABI数组, 以及带有 "abi" 字段的编译产物(hardhat/truffle)
+    pub fn from_json(json: &str) -> Result<Self> {
+        Self::from_value(serde_json::from_str(json)?)
+    }
+
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self> {
+        let file = File::open(path)?;
+        Self::from_value(serde_json::from_reader(file)?)
+    }
+
+    fn from_value(value: Value) -> Result<Self> {
+        let value = match value {
+            Value::Object(mut artifact) if artifact.contains_key("abi") => artifact.remove("abi").unwrap_or_default(),
+            value => value,
+        };
+        Ok(Self::new(serde_json::from_value(value)?))
+    }
+}
+
+impl ContractAbi for DynamicAbi {
+    fn abi(&self) -> &Contract {
+        &self.abi
+    }
+}
+
+// 将JSON参数按照ABI参数类型转换为Token
+pub fn json_to_tokens(kinds: &[ParamType], values: &[Value]) -> Result<Vec<Token>> {
+    if kinds.len() != values.len() {
+        return Err(Error::ErrArgument(format!("expected {} arguments, got {}", kinds.len(), values.len())));
+    }
+    kinds.iter().zip(values).map(|(kind, value)| json_to_token(kind, value)).collect()
+}
+
+pub fn json_to_token(kind: &ParamType, value: &Value) -> Result<Token> {
+    match (kind, value) {
+        (ParamType::Array(inner), Value::Array(values)) => {
+            let tokens = values.iter().map(|v| json_to_token(inner, v)).collect::<Result<Vec<_>>>()?;
+            Ok(Token::Array(tokens))
+        }
+        (ParamType::FixedArray(inner, len), Value::Array(values)) => {
+            if values.len() != *len {
+                return Err(Error::ErrArgument(format!("expected {} items for {}, got {}", len, kind, values.len())));
+            }
+            let tokens = values.iter().map(|v| json_to_token(inner, v)).collect::<Result<Vec<_>>>()?;
+            Ok(Token::FixedArray(tokens))
+        }
+        (ParamType::Tuple(kinds), Value::Array(values)) => {
+            Ok(Token::Tuple(json_to_tokens(kinds, values)?))
+        }
+        (ParamType::Array(_), _) | (ParamType::FixedArray(_, _), _) | (ParamType::Tuple(_), _) => {
+            Err(Error::ErrArgument(format!("expected json array for {}, got {}", kind, value)))
+        }
+        (ParamType::Uint(_), Value::String(s)) if s.starts_with("0x") => Ok(Token::Uint(parse_hex_uint(s)?)),
+        (ParamType::Int(_), Value::String(s)) if s.starts_with("0x") => Ok(Token::Int(parse_hex_uint(s)?)),
+        (ParamType::Address, Value::String(s)) | (ParamType::Bytes, Value::String(s)) | (ParamType::FixedBytes(_), Value::String(s)) => {
+            Ok(LenientTokenizer::tokenize(kind, s.trim_start_matches("0x"))?)
+        }
+        (_, Value::String(s)) => Ok(LenientTokenizer::tokenize(kind, s)?),
+        (_, Value::Number(_)) | (_, Value::Bool(_)) => Ok(LenientTokenizer::tokenize(kind, &value.to_string())?),
+        _ => Err(Error::ErrArgument(format!("unsupported json value for {}: {}", kind, value))),
+    }
+}
+
+fn parse_hex_uint(s: &str) -> Result<U256> {
+    U256::from_str(&s[2..]).map_err(|e| Error::ErrArgument(format!("invalid hex number {}: {}", s, e)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use serde_json::json;
+    use web3::ethabi::Token;
+    use web3::types::{Address, U256};
+    use crate::abi::ContractAbi;
+    use crate::abi::dynamic::DynamicAbi;
+    use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi};
+
+    const TRANSFER_ABI: &str = "[{\"inputs\":[{\"name\":\"recipient\",\"type\":\"address\"},{\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transfer\",\"outputs\":[{\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]";
+
+    #[test]
+    fn dynamic_abi_matches_erc20() {
+        let abi = DynamicAbi::from_json(TRANSFER_ABI).unwrap();
+        let to = Address::from_str("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199").unwrap();
+        let expected = Erc20Abi::new().transfer(to, U256::exp10(18)).unwrap();
+
+        let data = abi.method("transfer", &[Token::Address(to), Token::Uint(U256::exp10(18))]).unwrap();
+        assert_eq!(data, expected);
+
+        let data = abi.method_json("transfer", &[json!("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199"), json!("0xde0b6b3a7640000")]).unwrap();
+        assert_eq!(data, expected);
+
+        let data = abi.method_json("transfer", &[json!("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199"), json!("1000000000000000000")]).unwrap();
+        assert_eq!(data, expected);
+    }
+
+    #[test]
+    fn dynamic_abi_from_artifact() {
+        let artifact = format!("{{\"contractName\":\"Token\",\"abi\":{}}}", TRANSFER_ABI);
+        let abi = DynamicAbi::from_json(&artifact).unwrap();
+        assert!(abi.abi().function("transfer").is_ok());
+    }
+
+    #[test]
+    fn dynamic_abi_argument_count() {
+        let abi = DynamicAbi::from_json(TRANSFER_ABI).unwrap();
+        assert!(abi.method_json("transfer", &[json!(1)]).is_err());
+    }
+}
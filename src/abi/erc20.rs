@@ -1,9 +1,36 @@
 use web3::contract::tokens::{Detokenize, Tokenize};
-use crate::abi::{ContractAbi, Result};
-use web3::ethabi::{Token};
-use web3::types::{Address, Bytes, U256};
+use web3::ethabi::Token;
+use crate::abi::{ContractAbi, Error, Result};
+use web3::types::{Address, Bytes, Log, TransactionReceipt, U256};
 
-const ERC20_ABI_JSON: &str = "[{\"inputs\":[{\"internalType\":\"string\",\"name\":\"name_\",\"type\":\"string\"},{\"internalType\":\"string\",\"name\":\"symbol_\",\"type\":\"string\"}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Approval\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"OwnershipTransferred\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"from\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"to\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Transfer\",\"type\":\"event\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"}],\"name\":\"allowance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"approve\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"balanceOf\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"decimals\",\"outputs\":[{\"internalType\":\"uint8\",\"name\":\"\",\"type\":\"uint8\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"subtractedValue\",\"type\":\"uint256\"}],\"name\":\"decreaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getOwner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"addedValue\",\"type\":\"uint256\"}],\"name\":\"increaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"msgSender\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"name\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"renounceOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"symbol\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"totalSupply\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transfer\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"sender\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transferFrom\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"transferOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]";
+pub const ERC20_ABI_JSON: &str = "[{\"inputs\":[{\"internalType\":\"string\",\"name\":\"name_\",\"type\":\"string\"},{\"internalType\":\"string\",\"name\":\"symbol_\",\"type\":\"string\"}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Approval\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"OwnershipTransferred\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"from\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"to\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Transfer\",\"type\":\"event\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"}],\"name\":\"allowance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"approve\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"balanceOf\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"decimals\",\"outputs\":[{\"internalType\":\"uint8\",\"name\":\"\",\"type\":\"uint8\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"subtractedValue\",\"type\":\"uint256\"}],\"name\":\"decreaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getOwner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"addedValue\",\"type\":\"uint256\"}],\"name\":\"increaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"msgSender\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"name\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"renounceOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"symbol\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"totalSupply\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transfer\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"sender\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transferFrom\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"transferOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TransferEvent {
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ApprovalEvent {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct OwnershipTransferredEvent {
+    pub previous_owner: Address,
+    pub new_owner: Address,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Erc20Event {
+    Transfer(TransferEvent),
+    Approval(ApprovalEvent),
+    OwnershipTransferred(OwnershipTransferredEvent),
+}
 
 pub trait Erc20ContractAbi: ContractAbi {
     fn name(&self) -> Result<Bytes> {
@@ -11,8 +38,7 @@ pub trait Erc20ContractAbi: ContractAbi {
     }
 
     fn decode_name(&self, data: Bytes) -> Result<String> {
-        let tokens = self.decode_method("name", data)?;
-        Ok(String::from_tokens(tokens)?)
+        decode_metadata(self.decode_method("name", data.clone()), data)
     }
 
     fn symbol(&self) -> Result<Bytes> {
@@ -20,8 +46,7 @@ pub trait Erc20ContractAbi: ContractAbi {
     }
 
     fn decode_symbol(&self, data: Bytes) ->Result<String> {
-        let tokens = self.decode_method("symbol", data)?;
-        Ok(String::from_tokens(tokens)?)
+        decode_metadata(self.decode_method("symbol", data.clone()), data)
     }
 
     fn decimals(&self) -> Result<Bytes> {
@@ -51,10 +76,23 @@ pub trait Erc20ContractAbi: ContractAbi {
         Ok(U256::from_tokens(tokens)?)
     }
 
+    fn msg_sender(&self) -> Result<Bytes> {
+        self.method("msgSender", &().into_tokens())
+    }
+
+    fn decode_msg_sender(&self, data: Bytes) -> Result<Address> {
+        let tokens = self.decode_method("msgSender", data)?;
+        Ok(Address::from_tokens(tokens)?)
+    }
+
     fn transfer(&self, to: Address, amount: U256) -> Result<Bytes> {
         self.method("transfer", &(to, amount).into_tokens())
     }
 
+    fn decode_transfer(&self, data: Bytes) -> Result {
+        decode_success("transfer", self.decode_method("transfer", data.clone()), data)
+    }
+
     fn allowance(&self, owner: Address, spender: Address) -> Result<Bytes> {
         self.method("allowance", &(owner, spender).into_tokens())
     }
@@ -65,13 +103,21 @@ pub trait Erc20ContractAbi: ContractAbi {
     }
 
     fn approve(&self, spender: Address, amount: U256) -> Result<Bytes> {
-        self.method("allowance", &(spender, amount).into_tokens())
+        self.method("approve", &(spender, amount).into_tokens())
+    }
+
+    fn decode_approve(&self, data: Bytes) -> Result {
+        decode_success("approve", self.decode_method("approve", data.clone()), data)
     }
 
     fn transfer_from(&self, from: Address, to: Address, amount: U256) -> Result<Bytes> {
         self.method("transferFrom", &(from, to, amount).into_tokens())
     }
 
+    fn decode_transfer_from(&self, data: Bytes) -> Result {
+        decode_success("transferFrom", self.decode_method("transferFrom", data.clone()), data)
+    }
+
     fn increase_allowance(&self, spender: Address, added_value: U256) -> Result<Bytes> {
         self.method("increaseAllowance", &(spender, added_value).into_tokens())
     }
@@ -79,9 +125,53 @@ pub trait Erc20ContractAbi: ContractAbi {
     fn decrease_allowance(&self, spender: Address, subtracted_value: U256) -> Result<Bytes> {
         self.method("decreaseAllowance", &(spender, subtracted_value).into_tokens())
     }
+
+    fn decode_log(&self, log: &Log) -> Result<Erc20Event> {
+        let topic = log.topics.first().ok_or(Error::ErrUnknownEvent)?;
+        if *topic == self.event_topic("Transfer")? {
+            let (from, to, value) = Detokenize::from_tokens(self.decode_event("Transfer", log)?)?;
+            Ok(Erc20Event::Transfer(TransferEvent { from, to, value }))
+        } else if *topic == self.event_topic("Approval")? {
+            let (owner, spender, value) = Detokenize::from_tokens(self.decode_event("Approval", log)?)?;
+            Ok(Erc20Event::Approval(ApprovalEvent { owner, spender, value }))
+        } else if *topic == self.event_topic("OwnershipTransferred")? {
+            let (previous_owner, new_owner) = Detokenize::from_tokens(self.decode_event("OwnershipTransferred", log)?)?;
+            Ok(Erc20Event::OwnershipTransferred(OwnershipTransferredEvent { previous_owner, new_owner }))
+        } else {
+            Err(Error::ErrUnknownEvent)
+        }
+    }
+
+    // 解析交易回执中所有可识别的代币事件, 其他合约或无法解析的日志会被跳过
+    fn receipt_events(&self, receipt: &TransactionReceipt) -> Vec<Erc20Event> {
+        receipt.logs.iter().filter_map(|log| self.decode_log(log).ok()).collect()
+    }
 }
 
 
+// 早期代币(如MKR)的 name/symbol 返回 bytes32, 按string解析失败时去掉末尾的0后按utf8解析
+fn decode_metadata(tokens: Result<Vec<Token>>, data: Bytes) -> Result<String> {
+    match tokens.and_then(|tokens| Ok(String::from_tokens(tokens)?)) {
+        Ok(value) => Ok(value),
+        Err(_) if data.0.len() == 32 => {
+            let end = data.0.iter().rposition(|b| *b != 0).map(|i| i + 1).unwrap_or(0);
+            Ok(String::from_utf8_lossy(&data.0[..end]).into_owned())
+        }
+        Err(e) => Err(e),
+    }
+}
+
+// 部分代币(如USDT)的 transfer/approve/transferFrom 没有返回值, 空返回视为成功, 有返回值时必须为true
+fn decode_success(name: &str, tokens: Result<Vec<Token>>, data: Bytes) -> Result {
+    if data.0.is_empty() {
+        return Ok(());
+    }
+    match bool::from_tokens(tokens?)? {
+        true => Ok(()),
+        false => Err(Error::ErrReturnFalse(name.to_string())),
+    }
+}
+
 pub struct Erc20Abi {
     abi: web3::ethabi::Contract
 }
@@ -94,6 +184,12 @@ impl Erc20Abi {
     }
 }
 
+impl Default for Erc20Abi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl ContractAbi for Erc20Abi {
     fn abi(&self) -> &web3::ethabi::Contract {
         &self.abi
@@ -104,8 +200,12 @@ impl Erc20ContractAbi for Erc20Abi {}
 
 #[cfg(test)]
 mod tests{
+    use web3::contract::tokens::Tokenize;
     use web3::ethabi::Address;
-    use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi};
+    use web3::types::{Bytes, H256, TransactionReceipt, U256};
+    use crate::abi::{ContractAbi, Error};
+    use crate::abi::erc20::{ApprovalEvent, Erc20Abi, Erc20ContractAbi, Erc20Event, TransferEvent};
+    use crate::mock::make_log;
 
     #[test]
     fn erc20_abi() {
@@ -122,4 +222,83 @@ mod tests{
         println!("{:?}", hex::encode(&data.0));
     }
 
-}
\ No newline at end of file
+    #[test]
+    fn erc20_constructor() {
+        let erc20_abi = Erc20Abi::new();
+        let bytecode = Bytes(vec![0x60, 0x80, 0x60, 0x40]);
+        let data = erc20_abi.constructor(bytecode.clone(), &("Token".to_string(), "TK".to_string()).into_tokens()).expect("constructor err");
+        assert_eq!(&data.0[..4], &bytecode.0[..]);
+        // 两个string参数: 2个offset + 2个(长度+内容)
+        assert_eq!(data.0.len(), 4 + 32 * 6);
+        assert!(erc20_abi.constructor(bytecode, &[]).is_err());
+    }
+
+    fn value_data(value: U256) -> Vec<u8> {
+        let mut data = vec![0u8; 32];
+        value.to_big_endian(&mut data);
+        data
+    }
+
+    #[test]
+    fn erc20_decode_log() {
+        let erc20_abi = Erc20Abi::new();
+        let from = Address::random();
+        let to = Address::random();
+        let value = U256::exp10(18);
+
+        let log = make_log(vec![erc20_abi.event_topic("Transfer").unwrap(), H256::from(from), H256::from(to)], value_data(value));
+        assert_eq!(erc20_abi.decode_log(&log).unwrap(), Erc20Event::Transfer(TransferEvent { from, to, value }));
+
+        let log = make_log(vec![erc20_abi.event_topic("Approval").unwrap(), H256::from(from), H256::from(to)], value_data(value));
+        assert_eq!(erc20_abi.decode_log(&log).unwrap(), Erc20Event::Approval(ApprovalEvent { owner: from, spender: to, value }));
+
+        let log = make_log(vec![H256::random()], vec![]);
+        assert!(erc20_abi.decode_log(&log).is_err());
+    }
+
+    #[test]
+    fn erc20_receipt_events() {
+        let erc20_abi = Erc20Abi::new();
+        let from = Address::random();
+        let to = Address::random();
+        let topic = erc20_abi.event_topic("Transfer").unwrap();
+        let receipt = TransactionReceipt {
+            logs: vec![
+                make_log(vec![topic, H256::from(from), H256::from(to)], value_data(U256::one())),
+                // ERC721 的 Transfer 与 ERC20 签名相同, 但 tokenId 在 topic 中
+                make_log(vec![topic, H256::from(from), H256::from(to), H256::from_low_u64_be(1)], vec![]),
+                make_log(vec![H256::random()], vec![]),
+            ],
+            ..Default::default()
+        };
+        let events = erc20_abi.receipt_events(&receipt);
+        assert_eq!(events, vec![Erc20Event::Transfer(TransferEvent { from, to, value: U256::one() })]);
+    }
+
+    // 以下返回值取自主网 eth_call 的结果
+    #[test]
+    fn erc20_non_standard_metadata() {
+        let erc20_abi = Erc20Abi::new();
+        // DAI name(): 标准string
+        let dai = hex::decode("0000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000000e44616920537461626c65636f696e000000000000000000000000000000000000").unwrap();
+        assert_eq!(erc20_abi.decode_name(Bytes(dai)).unwrap(), "Dai Stablecoin");
+        // MKR name()/symbol(): bytes32
+        let mkr_name = hex::decode("4d616b6572000000000000000000000000000000000000000000000000000000").unwrap();
+        assert_eq!(erc20_abi.decode_name(Bytes(mkr_name)).unwrap(), "Maker");
+        let mkr_symbol = hex::decode("4d4b520000000000000000000000000000000000000000000000000000000000").unwrap();
+        assert_eq!(erc20_abi.decode_symbol(Bytes(mkr_symbol)).unwrap(), "MKR");
+        assert!(erc20_abi.decode_symbol(Bytes(vec![0x4d; 5])).is_err());
+    }
+
+    #[test]
+    fn erc20_non_standard_return() {
+        let erc20_abi = Erc20Abi::new();
+        // USDT transfer()/approve(): 没有返回值
+        assert!(erc20_abi.decode_transfer(Bytes(vec![])).is_ok());
+        assert!(erc20_abi.decode_approve(Bytes(vec![])).is_ok());
+        assert!(erc20_abi.decode_transfer_from(Bytes(vec![])).is_ok());
+        assert!(erc20_abi.decode_transfer(Bytes(value_data(U256::one()))).is_ok());
+        assert!(matches!(erc20_abi.decode_transfer(Bytes(value_data(U256::zero()))), Err(Error::ErrReturnFalse(_))));
+        assert!(erc20_abi.decode_approve(Bytes(vec![1])).is_err());
+    }
+}
@@ -0,0 +1,102 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::types::{Address, Bytes, U256};
+use crate::abi::{ContractAbi, Result};
+
+// OpenZeppelin ERC20 的可选扩展: ERC20Mintable(AccessControl/Ownable 下的 mint), ERC20Burnable, ERC20Pausable, ERC20Capped
+pub const ERC20_EXTENSIONS_ABI_JSON: &str = "[{\"inputs\":[{\"internalType\":\"address\",\"name\":\"to\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"mint\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"burn\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"burnFrom\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"pause\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"unpause\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"paused\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"cap\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]";
+
+pub trait MintableContractAbi: ContractAbi {
+    fn mint(&self, to: Address, amount: U256) -> Result<Bytes> {
+        self.method("mint", &(to, amount).into_tokens())
+    }
+}
+
+pub trait BurnableContractAbi: ContractAbi {
+    fn burn(&self, amount: U256) -> Result<Bytes> {
+        self.method("burn", &amount.into_tokens())
+    }
+
+    fn burn_from(&self, account: Address, amount: U256) -> Result<Bytes> {
+        self.method("burnFrom", &(account, amount).into_tokens())
+    }
+}
+
+pub trait PausableContractAbi: ContractAbi {
+    fn pause(&self) -> Result<Bytes> {
+        self.method("pause", &().into_tokens())
+    }
+
+    fn unpause(&self) -> Result<Bytes> {
+        self.method("unpause", &().into_tokens())
+    }
+
+    fn paused(&self) -> Result<Bytes> {
+        self.method("paused", &().into_tokens())
+    }
+
+    fn decode_paused(&self, data: Bytes) -> Result<bool> {
+        let tokens = self.decode_method("paused", data)?;
+        Ok(bool::from_tokens(tokens)?)
+    }
+}
+
+pub trait CappedContractAbi: ContractAbi {
+    fn cap(&self) -> Result<Bytes> {
+        self.method("cap", &().into_tokens())
+    }
+
+    fn decode_cap(&self, data: Bytes) -> Result<U256> {
+        let tokens = self.decode_method("cap", data)?;
+        Ok(U256::from_tokens(tokens)?)
+    }
+}
+
+pub struct Erc20ExtensionsAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl Erc20ExtensionsAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(ERC20_EXTENSIONS_ABI_JSON.as_bytes()).expect("ERC20_EXTENSIONS_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for Erc20ExtensionsAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for Erc20ExtensionsAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl MintableContractAbi for Erc20ExtensionsAbi {}
+
+impl BurnableContractAbi for Erc20ExtensionsAbi {}
+
+impl PausableContractAbi for Erc20ExtensionsAbi {}
+
+impl CappedContractAbi for Erc20ExtensionsAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, U256};
+    use crate::abi::extensions::{BurnableContractAbi, CappedContractAbi, Erc20ExtensionsAbi, MintableContractAbi, PausableContractAbi};
+
+    #[test]
+    fn extensions_abi() {
+        let abi = Erc20ExtensionsAbi::new();
+        assert_eq!(hex::encode(&abi.mint(Address::random(), U256::one()).unwrap().0[..4]), "40c10f19");
+        assert_eq!(hex::encode(&abi.burn(U256::one()).unwrap().0[..4]), "42966c68");
+        assert_eq!(hex::encode(&abi.burn_from(Address::random(), U256::one()).unwrap().0[..4]), "79cc6790");
+        assert_eq!(hex::encode(abi.pause().unwrap().0), "8456cb59");
+        assert_eq!(hex::encode(abi.unpause().unwrap().0), "3f4ba83a");
+        assert_eq!(hex::encode(abi.paused().unwrap().0), "5c975abb");
+        assert_eq!(hex::encode(abi.cap().unwrap().0), "355274ea");
+    }
+}
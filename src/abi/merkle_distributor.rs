@@ -0,0 +1,84 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::ethabi::Token;
+use web3::types::{Address, Bytes, H256, U256};
+use crate::abi::{ContractAbi, Result};
+
+// Uniswap MerkleDistributor, 叶子为 keccak256(abi.encodePacked(index, account, amount))
+pub const MERKLE_DISTRIBUTOR_ABI_JSON: &str = "[{\"inputs\":[],\"name\":\"token\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"merkleRoot\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"index\",\"type\":\"uint256\"}],\"name\":\"isClaimed\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"uint256\",\"name\":\"index\",\"type\":\"uint256\"},{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"},{\"internalType\":\"bytes32[]\",\"name\":\"merkleProof\",\"type\":\"bytes32[]\"}],\"name\":\"claim\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"index\",\"type\":\"uint256\"},{\"indexed\":false,\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"Claimed\",\"type\":\"event\"}]";
+
+pub trait MerkleDistributorContractAbi: ContractAbi {
+    fn token(&self) -> Result<Bytes> {
+        self.method("token", &().into_tokens())
+    }
+
+    fn decode_token(&self, data: Bytes) -> Result<Address> {
+        let tokens = self.decode_method("token", data)?;
+        Ok(Address::from_tokens(tokens)?)
+    }
+
+    fn merkle_root(&self) -> Result<Bytes> {
+        self.method("merkleRoot", &().into_tokens())
+    }
+
+    fn decode_merkle_root(&self, data: Bytes) -> Result<H256> {
+        let tokens = self.decode_method("merkleRoot", data)?;
+        Ok(H256::from_tokens(tokens)?)
+    }
+
+    fn is_claimed(&self, index: U256) -> Result<Bytes> {
+        self.method("isClaimed", &index.into_tokens())
+    }
+
+    fn decode_is_claimed(&self, data: Bytes) -> Result<bool> {
+        let tokens = self.decode_method("isClaimed", data)?;
+        Ok(bool::from_tokens(tokens)?)
+    }
+
+    // 任何人都可以替 account 领取, 代币总是转给 account
+    fn claim(&self, index: U256, account: Address, amount: U256, proof: &[H256]) -> Result<Bytes> {
+        let proof = Token::Array(proof.iter().map(|h| Token::FixedBytes(h.as_bytes().to_vec())).collect());
+        self.method("claim", &[Token::Uint(index), Token::Address(account), Token::Uint(amount), proof])
+    }
+}
+
+pub struct MerkleDistributorAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl MerkleDistributorAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(MERKLE_DISTRIBUTOR_ABI_JSON.as_bytes()).expect("MERKLE_DISTRIBUTOR_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for MerkleDistributorAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for MerkleDistributorAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl MerkleDistributorContractAbi for MerkleDistributorAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{Address, H256, U256};
+    use crate::abi::merkle_distributor::{MerkleDistributorAbi, MerkleDistributorContractAbi};
+
+    #[test]
+    fn merkle_distributor_abi() {
+        let abi = MerkleDistributorAbi::new();
+        let data = abi.claim(U256::one(), Address::random(), U256::from(100), &[H256::random(), H256::random()]).unwrap();
+        assert_eq!(hex::encode(&data.0[..4]), "2e7ba6ef");
+        // 3个静态参数 + offset + 长度 + 2个元素
+        assert_eq!(data.0.len(), 4 + 32 * 7);
+        assert_eq!(hex::encode(&abi.is_claimed(U256::one()).unwrap().0[..4]), "9e34070f");
+    }
+}
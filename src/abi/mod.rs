@@ -1,8 +1,17 @@
-use web3::ethabi::Token;
-use web3::types::{Bytes};
+use serde_json::Value;
+use web3::ethabi::{RawLog, Token};
+use web3::types::{Bytes, H256, Log};
 use thiserror::Error;
 
 pub mod erc20;
+pub mod dynamic;
+pub mod multicall;
+pub mod permit;
+pub mod ownable;
+pub mod access_control;
+pub mod extensions;
+pub mod disperse;
+pub mod merkle_distributor;
 
 pub type Result<T = ()> = std::result::Result<T, Error>;
 
@@ -14,20 +23,55 @@ pub enum Error {
     ErrAddress(#[from] hex::FromHexError),
     #[error("contract error")]
     ErrWeb3Contract(#[from] web3::contract::Error),
+    #[error("json error")]
+    ErrJson(#[from] serde_json::Error),
+    #[error("io error")]
+    ErrIo(#[from] std::io::Error),
+    #[error("invalid argument: {0}")]
+    ErrArgument(String),
+    #[error("unknown event log")]
+    ErrUnknownEvent,
+    #[error("{0} returned false")]
+    ErrReturnFalse(String),
 }
 
 pub trait ContractAbi {
     fn abi(&self) -> &web3::ethabi::Contract;
 
+    fn constructor(&self, bytecode: Bytes, tokens: &[Token]) -> Result<Bytes> {
+        match self.abi().constructor() {
+            Some(c) => Ok(Bytes(c.encode_input(bytecode.0, tokens)?)),
+            None if tokens.is_empty() => Ok(bytecode),
+            None => Err(Error::ErrArgument("abi has no constructor".to_string())),
+        }
+    }
+
     fn method(&self, name: &str, tokens: &[Token]) -> Result<Bytes> {
         let f = self.abi().function(name)?;
         let b = f.encode_input(tokens)?;
         Ok(Bytes(b))
     }
 
+    fn method_json(&self, name: &str, values: &[Value]) -> Result<Bytes> {
+        let f = self.abi().function(name)?;
+        let kinds: Vec<_> = f.inputs.iter().map(|p| p.kind.clone()).collect();
+        let tokens = dynamic::json_to_tokens(&kinds, values)?;
+        self.method(name, &tokens)
+    }
+
     fn decode_method(&self, name: &str, data: Bytes) -> Result<Vec<Token>> {
         let f = self.abi().function(name)?;
         let res = f.decode_output(&data.0)?;
         Ok(res)
     }
-}
\ No newline at end of file
+
+    fn event_topic(&self, name: &str) -> Result<H256> {
+        Ok(self.abi().event(name)?.signature())
+    }
+
+    fn decode_event(&self, name: &str, log: &Log) -> Result<Vec<Token>> {
+        let e = self.abi().event(name)?;
+        let log = e.parse_log(RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        Ok(log.params.into_iter().map(|p| p.value).collect())
+    }
+}
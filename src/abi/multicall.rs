@@ -0,0 +1,133 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::ethabi::Token;
+use web3::types::{Address, Bytes, U256};
+use crate::abi::{ContractAbi, Error, Result};
+
+pub const MULTICALL3_ABI_JSON: &str = "[{\"inputs\":[{\"components\":[{\"internalType\":\"address\",\"name\":\"target\",\"type\":\"address\"},{\"internalType\":\"bool\",\"name\":\"allowFailure\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"callData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Call3[]\",\"name\":\"calls\",\"type\":\"tuple[]\"}],\"name\":\"aggregate3\",\"outputs\":[{\"components\":[{\"internalType\":\"bool\",\"name\":\"success\",\"type\":\"bool\"},{\"internalType\":\"bytes\",\"name\":\"returnData\",\"type\":\"bytes\"}],\"internalType\":\"struct Multicall3.Result[]\",\"name\":\"returnData\",\"type\":\"tuple[]\"}],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"addr\",\"type\":\"address\"}],\"name\":\"getEthBalance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"balance\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call3 {
+    pub target: Address,
+    pub allow_failure: bool,
+    pub call_data: Bytes,
+}
+
+impl Call3 {
+    pub fn new(target: Address, call_data: Bytes) -> Self {
+        Self { target, allow_failure: false, call_data }
+    }
+
+    pub fn allow_failure(target: Address, call_data: Bytes) -> Self {
+        Self { target, allow_failure: true, call_data }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Call3Result {
+    pub success: bool,
+    pub return_data: Bytes,
+}
+
+impl Call3Result {
+    // 调用失败时返回 None, 否则使用对应abi的 decode_xxx 解析返回值
+    pub fn decode<R, F: FnOnce(Bytes) -> Result<R>>(self, f: F) -> Option<R> {
+        if !self.success {
+            return None;
+        }
+        f(self.return_data).ok()
+    }
+}
+
+pub trait MulticallContractAbi: ContractAbi {
+    fn aggregate3(&self, calls: &[Call3]) -> Result<Bytes> {
+        let calls = calls.iter().map(|c| Token::Tuple(vec![
+            Token::Address(c.target),
+            Token::Bool(c.allow_failure),
+            Token::Bytes(c.call_data.0.clone()),
+        ])).collect();
+        self.method("aggregate3", &[Token::Array(calls)])
+    }
+
+    fn decode_aggregate3(&self, data: Bytes) -> Result<Vec<Call3Result>> {
+        let tokens = self.decode_method("aggregate3", data)?;
+        let results = match tokens.into_iter().next() {
+            Some(Token::Array(results)) => results,
+            _ => return Err(Error::ErrEthAbi(web3::ethabi::Error::InvalidData)),
+        };
+        results.into_iter().map(|r| {
+            let (success, return_data): (bool, Vec<u8>) = match r {
+                Token::Tuple(r) => Detokenize::from_tokens(r)?,
+                _ => return Err(Error::ErrEthAbi(web3::ethabi::Error::InvalidData)),
+            };
+            Ok(Call3Result { success, return_data: Bytes(return_data) })
+        }).collect()
+    }
+
+    fn get_eth_balance(&self, address: Address) -> Result<Bytes> {
+        self.method("getEthBalance", &address.into_tokens())
+    }
+
+    fn decode_get_eth_balance(&self, data: Bytes) -> Result<U256> {
+        let tokens = self.decode_method("getEthBalance", data)?;
+        Ok(U256::from_tokens(tokens)?)
+    }
+}
+
+pub struct MulticallAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl MulticallAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(MULTICALL3_ABI_JSON.as_bytes()).expect("MULTICALL3_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for MulticallAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for MulticallAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl MulticallContractAbi for MulticallAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::ethabi::{encode, Token};
+    use web3::types::{Address, Bytes, U256};
+    use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi};
+    use crate::abi::multicall::{Call3, Call3Result, MulticallAbi, MulticallContractAbi};
+
+    #[test]
+    fn multicall_aggregate3() {
+        let abi = MulticallAbi::new();
+        let erc20_abi = Erc20Abi::new();
+        let token = Address::random();
+        let calls = vec![
+            Call3::new(token, erc20_abi.decimals().unwrap()),
+            Call3::allow_failure(token, erc20_abi.balance_of(Address::random()).unwrap()),
+        ];
+        let data = abi.aggregate3(&calls).unwrap();
+        assert_eq!(hex::encode(&data.0[..4]), "82ad56cb");
+
+        let mut balance = vec![0u8; 32];
+        U256::exp10(18).to_big_endian(&mut balance);
+        let output = encode(&[Token::Array(vec![
+            Token::Tuple(vec![Token::Bool(true), Token::Bytes(balance)]),
+            Token::Tuple(vec![Token::Bool(false), Token::Bytes(vec![])]),
+        ])]);
+        let results = abi.decode_aggregate3(Bytes(output)).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].clone().decode(|d| erc20_abi.decode_balance_of(d)), Some(U256::exp10(18)));
+        assert_eq!(results[1].clone().decode(|d| erc20_abi.decode_balance_of(d)), None);
+        assert_eq!(results[1], Call3Result { success: false, return_data: Bytes(vec![]) });
+    }
+}
@@ -0,0 +1,95 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::types::{Address, Bytes};
+use crate::abi::{ContractAbi, Result};
+
+// OpenZeppelin Ownable / Ownable2Step, getOwner 为 BEP20 的写法
+pub const OWNABLE_ABI_JSON: &str = "[{\"inputs\":[],\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getOwner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"pendingOwner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"transferOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"acceptOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"renounceOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"OwnershipTransferred\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"OwnershipTransferStarted\",\"type\":\"event\"}]";
+
+pub trait OwnableContractAbi: ContractAbi {
+    fn owner(&self) -> Result<Bytes> {
+        self.method("owner", &().into_tokens())
+    }
+
+    fn decode_owner(&self, data: Bytes) -> Result<Address> {
+        let tokens = self.decode_method("owner", data)?;
+        Ok(Address::from_tokens(tokens)?)
+    }
+
+    fn get_owner(&self) -> Result<Bytes> {
+        self.method("getOwner", &().into_tokens())
+    }
+
+    fn decode_get_owner(&self, data: Bytes) -> Result<Address> {
+        let tokens = self.decode_method("getOwner", data)?;
+        Ok(Address::from_tokens(tokens)?)
+    }
+
+    fn pending_owner(&self) -> Result<Bytes> {
+        self.method("pendingOwner", &().into_tokens())
+    }
+
+    fn decode_pending_owner(&self, data: Bytes) -> Result<Address> {
+        let tokens = self.decode_method("pendingOwner", data)?;
+        Ok(Address::from_tokens(tokens)?)
+    }
+
+    fn transfer_ownership(&self, new_owner: Address) -> Result<Bytes> {
+        self.method("transferOwnership", &new_owner.into_tokens())
+    }
+
+    fn accept_ownership(&self) -> Result<Bytes> {
+        self.method("acceptOwnership", &().into_tokens())
+    }
+
+    fn renounce_ownership(&self) -> Result<Bytes> {
+        self.method("renounceOwnership", &().into_tokens())
+    }
+}
+
+pub struct OwnableAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl OwnableAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: web3::ethabi::Contract::load(OWNABLE_ABI_JSON.as_bytes()).expect("OWNABLE_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for OwnableAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for OwnableAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl OwnableContractAbi for OwnableAbi {}
+
+#[cfg(test)]
+mod tests {
+    use web3::ethabi::{encode, Token};
+    use web3::types::{Address, Bytes};
+    use crate::abi::ContractAbi;
+    use crate::abi::ownable::{OwnableAbi, OwnableContractAbi};
+
+    #[test]
+    fn ownable_abi() {
+        let abi = OwnableAbi::new();
+        assert_eq!(hex::encode(abi.owner().unwrap().0), "8da5cb5b");
+        assert_eq!(hex::encode(abi.pending_owner().unwrap().0), "e30c3978");
+        assert_eq!(hex::encode(abi.accept_ownership().unwrap().0), "79ba5097");
+        assert_eq!(hex::encode(abi.renounce_ownership().unwrap().0), "715018a6");
+        assert_eq!(hex::encode(&abi.transfer_ownership(Address::random()).unwrap().0[..4]), "f2fde38b");
+
+        let owner = Address::random();
+        assert_eq!(abi.decode_owner(Bytes(encode(&[Token::Address(owner)]))).unwrap(), owner);
+        assert!(abi.event_topic("OwnershipTransferStarted").is_ok());
+    }
+}
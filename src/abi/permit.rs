@@ -0,0 +1,171 @@
+use web3::contract::tokens::{Detokenize, Tokenize};
+use web3::ethabi::{encode, Token};
+use web3::signing::keccak256;
+use web3::types::{Address, Bytes, H256, U256};
+use crate::abi::{ContractAbi, Result};
+use crate::abi::erc20::{Erc20ContractAbi, ERC20_ABI_JSON};
+
+// EIP-2612 在ERC20基础上增加的方法
+pub const ERC20_PERMIT_ABI_JSON: &str = "[{\"inputs\":[{\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"},{\"internalType\":\"uint256\",\"name\":\"deadline\",\"type\":\"uint256\"},{\"internalType\":\"uint8\",\"name\":\"v\",\"type\":\"uint8\"},{\"internalType\":\"bytes32\",\"name\":\"r\",\"type\":\"bytes32\"},{\"internalType\":\"bytes32\",\"name\":\"s\",\"type\":\"bytes32\"}],\"name\":\"permit\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"}],\"name\":\"nonces\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"DOMAIN_SEPARATOR\",\"outputs\":[{\"internalType\":\"bytes32\",\"name\":\"\",\"type\":\"bytes32\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"version\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"}]";
+
+pub const EIP712_DOMAIN_TYPE: &str = "EIP712Domain(string name,string version,uint256 chainId,address verifyingContract)";
+pub const PERMIT_TYPE: &str = "Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)";
+
+// Permit 签名消息
+#[derive(Debug, Clone, PartialEq)]
+pub struct Permit {
+    pub owner: Address,
+    pub spender: Address,
+    pub value: U256,
+    pub nonce: U256,
+    pub deadline: U256,
+}
+
+impl Permit {
+    pub fn struct_hash(&self) -> H256 {
+        H256::from(keccak256(&encode(&[
+            Token::FixedBytes(keccak256(PERMIT_TYPE.as_bytes()).to_vec()),
+            Token::Address(self.owner),
+            Token::Address(self.spender),
+            Token::Uint(self.value),
+            Token::Uint(self.nonce),
+            Token::Uint(self.deadline),
+        ])))
+    }
+
+    // EIP-712 签名的消息hash: keccak256(0x1901 ++ domainSeparator ++ structHash)
+    pub fn digest(&self, domain_separator: H256) -> H256 {
+        let mut data = vec![0x19, 0x01];
+        data.extend_from_slice(domain_separator.as_bytes());
+        data.extend_from_slice(self.struct_hash().as_bytes());
+        H256::from(keccak256(&data))
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PermitSignature {
+    pub v: u8,
+    pub r: H256,
+    pub s: H256,
+}
+
+// 合约没有 DOMAIN_SEPARATOR() 时, 按 name/version/chainId/合约地址 计算
+pub fn domain_separator(name: &str, version: &str, chain_id: U256, contract: Address) -> H256 {
+    H256::from(keccak256(&encode(&[
+        Token::FixedBytes(keccak256(EIP712_DOMAIN_TYPE.as_bytes()).to_vec()),
+        Token::FixedBytes(keccak256(name.as_bytes()).to_vec()),
+        Token::FixedBytes(keccak256(version.as_bytes()).to_vec()),
+        Token::Uint(chain_id),
+        Token::Address(contract),
+    ])))
+}
+
+pub trait Erc20PermitContractAbi: Erc20ContractAbi {
+    fn nonces(&self, owner: Address) -> Result<Bytes> {
+        self.method("nonces", &owner.into_tokens())
+    }
+
+    fn decode_nonces(&self, data: Bytes) -> Result<U256> {
+        let tokens = self.decode_method("nonces", data)?;
+        Ok(U256::from_tokens(tokens)?)
+    }
+
+    fn domain_separator(&self) -> Result<Bytes> {
+        self.method("DOMAIN_SEPARATOR", &().into_tokens())
+    }
+
+    fn decode_domain_separator(&self, data: Bytes) -> Result<H256> {
+        let tokens = self.decode_method("DOMAIN_SEPARATOR", data)?;
+        Ok(H256::from_tokens(tokens)?)
+    }
+
+    fn version(&self) -> Result<Bytes> {
+        self.method("version", &().into_tokens())
+    }
+
+    fn decode_version(&self, data: Bytes) -> Result<String> {
+        let tokens = self.decode_method("version", data)?;
+        Ok(String::from_tokens(tokens)?)
+    }
+
+    fn permit(&self, permit: &Permit, signature: &PermitSignature) -> Result<Bytes> {
+        self.method("permit", &[
+            Token::Address(permit.owner),
+            Token::Address(permit.spender),
+            Token::Uint(permit.value),
+            Token::Uint(permit.deadline),
+            Token::Uint(U256::from(signature.v)),
+            Token::FixedBytes(signature.r.as_bytes().to_vec()),
+            Token::FixedBytes(signature.s.as_bytes().to_vec()),
+        ])
+    }
+}
+
+pub struct Erc20PermitAbi {
+    abi: web3::ethabi::Contract
+}
+
+impl Erc20PermitAbi {
+    pub fn new() -> Self {
+        let mut functions: Vec<serde_json::Value> = serde_json::from_str(ERC20_ABI_JSON).expect("ERC20_ABI_JSON is error");
+        let permit: Vec<serde_json::Value> = serde_json::from_str(ERC20_PERMIT_ABI_JSON).expect("ERC20_PERMIT_ABI_JSON is error");
+        functions.extend(permit);
+        Self {
+            abi: serde_json::from_value(serde_json::Value::Array(functions)).expect("ERC20_PERMIT_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for Erc20PermitAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ContractAbi for Erc20PermitAbi {
+    fn abi(&self) -> &web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl Erc20ContractAbi for Erc20PermitAbi {}
+
+impl Erc20PermitContractAbi for Erc20PermitAbi {}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use web3::signing::keccak256;
+    use web3::types::{Address, H256, U256};
+    use crate::abi::erc20::Erc20ContractAbi;
+    use crate::abi::permit::{domain_separator, Erc20PermitAbi, Erc20PermitContractAbi, Permit, PermitSignature, EIP712_DOMAIN_TYPE, PERMIT_TYPE};
+
+    #[test]
+    fn permit_abi() {
+        let abi = Erc20PermitAbi::new();
+        assert_eq!(hex::encode(&abi.nonces(Address::random()).unwrap().0[..4]), "7ecebe00");
+        assert_eq!(hex::encode(&abi.domain_separator().unwrap().0[..4]), "3644e515");
+        assert_eq!(hex::encode(&abi.name().unwrap().0[..4]), "06fdde03");
+
+        let permit = Permit { owner: Address::random(), spender: Address::random(), value: U256::one(), nonce: U256::zero(), deadline: U256::MAX };
+        let signature = PermitSignature { v: 27, r: H256::random(), s: H256::random() };
+        let data = abi.permit(&permit, &signature).unwrap();
+        assert_eq!(hex::encode(&data.0[..4]), "d505accf");
+        assert_eq!(data.0.len(), 4 + 32 * 7);
+    }
+
+    #[test]
+    fn permit_typed_data() {
+        assert_eq!(hex::encode(keccak256(EIP712_DOMAIN_TYPE.as_bytes())), "8b73c3c69bb8fe3d512ecc4cf759cc79239f7b179b0ffacaa9a75d522b39400f");
+        assert_eq!(hex::encode(keccak256(PERMIT_TYPE.as_bytes())), "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9");
+
+        // EIP-712 规范中 Ether Mail 示例的 domain
+        let contract = Address::from_str("0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC").unwrap();
+        let separator = domain_separator("Ether Mail", "1", U256::one(), contract);
+        assert_eq!(hex::encode(separator), "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f");
+
+        let permit = Permit { owner: Address::random(), spender: Address::random(), value: U256::one(), nonce: U256::zero(), deadline: U256::MAX };
+        assert_ne!(permit.digest(separator), permit.digest(domain_separator("Ether Mail", "2", U256::one(), contract)));
+        assert_ne!(permit.struct_hash(), Permit { nonce: U256::one(), ..permit.clone() }.struct_hash());
+    }
+}
@@ -0,0 +1,180 @@
+// 带精度的代币数量, raw 为链上的整数值, decimals 为代币精度
+// 例如 decimals = 6 时, "1.25" 对应 raw = 1250000
+
+use std::fmt;
+use thiserror::Error;
+use web3::types::U256;
+use crate::utils::units::{self, format_units, parse_units, MAX_DECIMALS};
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid amount: {0}")]
+    ErrParse(String),
+    #[error("too many fractional digits: {0}, token decimals {1}")]
+    ErrPrecision(usize, u8),
+    #[error("decimals mismatch: {0} != {1}")]
+    ErrDecimals(u8, u8),
+    #[error("invalid decimals: {0}")]
+    ErrInvalidDecimals(U256),
+    #[error("amount overflow")]
+    ErrOverflow,
+}
+
+impl From<units::Error> for Error {
+    fn from(e: units::Error) -> Self {
+        match e {
+            units::Error::ErrParse(value) | units::Error::ErrUnit(value) => Error::ErrParse(value),
+            units::Error::ErrPrecision(digits, decimals) => Error::ErrPrecision(digits, decimals),
+            units::Error::ErrOverflow => Error::ErrOverflow,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Amount {
+    pub raw: U256,
+    pub decimals: u8,
+}
+
+impl Amount {
+    pub fn new(raw: U256, decimals: u8) -> Self {
+        Self { raw, decimals }
+    }
+
+    pub fn zero(decimals: u8) -> Self {
+        Self::new(U256::zero(), decimals)
+    }
+
+    // 合约 decimals() 返回的是 uint, 超出范围的视为错误
+    pub fn from_decimals(raw: U256, decimals: U256) -> Result<Self> {
+        if decimals > U256::from(MAX_DECIMALS) {
+            return Err(Error::ErrInvalidDecimals(decimals));
+        }
+        Ok(Self::new(raw, decimals.as_u32() as u8))
+    }
+
+    // 解析 "1.25" / "1,000.5" 这样的字符串, 小数位数不能超过 decimals.
+    // 整数部分可以带 Display 输出的千分位逗号, 每组必须是3位
+    pub fn parse(value: &str, decimals: u8) -> Result<Self> {
+        if decimals > MAX_DECIMALS {
+            return Err(Error::ErrInvalidDecimals(U256::from(decimals)));
+        }
+        let value = value.trim();
+        let (integer, fraction) = value.split_at(value.find('.').unwrap_or(value.len()));
+        let mut groups = integer.split(',');
+        let first = groups.next().unwrap_or_default();
+        if integer.contains(',') && (first.is_empty() || first.len() > 3 || !groups.all(|group| group.len() == 3)) {
+            return Err(Error::ErrParse(value.to_string()));
+        }
+        let raw = parse_units(&format!("{}{}", integer.replace(',', ""), fraction), decimals)?;
+        Ok(Self::new(raw, decimals))
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.raw.is_zero()
+    }
+
+    pub fn checked_add(&self, other: Amount) -> Result<Self> {
+        self.check_decimals(&other)?;
+        let raw = self.raw.checked_add(other.raw).ok_or(Error::ErrOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    pub fn checked_sub(&self, other: Amount) -> Result<Self> {
+        self.check_decimals(&other)?;
+        let raw = self.raw.checked_sub(other.raw).ok_or(Error::ErrOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    pub fn checked_mul(&self, factor: U256) -> Result<Self> {
+        let raw = self.raw.checked_mul(factor).ok_or(Error::ErrOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    pub fn checked_div(&self, divisor: U256) -> Result<Self> {
+        let raw = self.raw.checked_div(divisor).ok_or(Error::ErrOverflow)?;
+        Ok(Self::new(raw, self.decimals))
+    }
+
+    // 不带千位分隔符, 去掉小数末尾的0
+    pub fn to_plain_string(&self) -> String {
+        format_units(self.raw, self.decimals).unwrap_or_default()
+    }
+
+    fn check_decimals(&self, other: &Amount) -> Result {
+        if self.decimals != other.decimals {
+            return Err(Error::ErrDecimals(self.decimals, other.decimals));
+        }
+        Ok(())
+    }
+}
+
+// 整数部分按千位分组, 如 1,234,567.89
+impl fmt::Display for Amount {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let plain = self.to_plain_string();
+        let (integer, fraction) = plain.split_once('.').unwrap_or((&plain, ""));
+        let mut grouped = String::with_capacity(integer.len() + integer.len() / 3);
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push(',');
+            }
+            grouped.push(c);
+        }
+        if fraction.is_empty() {
+            write!(f, "{}", grouped)
+        } else {
+            write!(f, "{}.{}", grouped, fraction)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::U256;
+    use crate::amount::{Amount, Error};
+
+    #[test]
+    fn amount_parse() {
+        assert_eq!(Amount::parse("1.25", 6).unwrap().raw, U256::from(1_250_000));
+        assert_eq!(Amount::parse("1,000", 2).unwrap().raw, U256::from(100_000));
+        assert_eq!(Amount::parse(".5", 18).unwrap().raw, U256::exp10(17) * 5);
+        assert_eq!(Amount::parse("2.500", 1).unwrap().raw, U256::from(25));
+        assert_eq!(Amount::parse("1.234", 2), Err(Error::ErrPrecision(3, 2)));
+        assert!(matches!(Amount::parse("-1", 18), Err(Error::ErrParse(_))));
+        assert!(matches!(Amount::parse("1.2.3", 18), Err(Error::ErrParse(_))));
+        assert!(matches!(Amount::parse(".", 18), Err(Error::ErrParse(_))));
+        assert_eq!(Amount::parse(&"9".repeat(70), 18), Err(Error::ErrOverflow));
+        assert_eq!(Amount::parse("12,345,678.9", 1).unwrap().raw, U256::from(123_456_789));
+        for value in ["1,5", "1,0000", ",100", "1,,000", "1000,", "1.000,5", "1_000"] {
+            assert!(matches!(Amount::parse(value, 18), Err(Error::ErrParse(_))), "{}", value);
+        }
+        assert!(Amount::from_decimals(U256::one(), U256::from(255)).is_err());
+    }
+
+    #[test]
+    fn amount_format() {
+        assert_eq!(Amount::new(U256::from(1_234_567_890u64), 2).to_string(), "12,345,678.9");
+        assert_eq!(Amount::new(U256::from(5), 6).to_string(), "0.000005");
+        assert_eq!(Amount::new(U256::exp10(21), 18).to_string(), "1,000");
+        assert_eq!(Amount::new(U256::exp10(21), 18).to_plain_string(), "1000");
+        assert_eq!(Amount::zero(18).to_string(), "0");
+        let amount = Amount::parse("123456.789", 18).unwrap();
+        assert_eq!(Amount::parse(&amount.to_string(), 18).unwrap(), amount);
+    }
+
+    #[test]
+    fn amount_arithmetic() {
+        let a = Amount::parse("1.5", 6).unwrap();
+        let b = Amount::parse("0.25", 6).unwrap();
+        assert_eq!(a.checked_add(b).unwrap().to_string(), "1.75");
+        assert_eq!(a.checked_sub(b).unwrap().to_string(), "1.25");
+        assert_eq!(b.checked_sub(a), Err(Error::ErrOverflow));
+        assert_eq!(a.checked_mul(U256::from(3)).unwrap().to_string(), "4.5");
+        assert_eq!(a.checked_div(U256::zero()), Err(Error::ErrOverflow));
+        assert_eq!(a.checked_add(Amount::zero(18)), Err(Error::ErrDecimals(6, 18)));
+        assert_eq!(Amount::new(U256::MAX, 0).checked_add(Amount::new(U256::one(), 0)), Err(Error::ErrOverflow));
+    }
+}
@@ -0,0 +1,387 @@
+// 由 crate::codegen 生成, 请勿手动修改
+
+pub const ERC20_ABI_JSON: &str = "[{\"inputs\":[{\"internalType\":\"string\",\"name\":\"name_\",\"type\":\"string\"},{\"internalType\":\"string\",\"name\":\"symbol_\",\"type\":\"string\"}],\"stateMutability\":\"nonpayable\",\"type\":\"constructor\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Approval\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"previousOwner\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"OwnershipTransferred\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"internalType\":\"address\",\"name\":\"from\",\"type\":\"address\"},{\"indexed\":true,\"internalType\":\"address\",\"name\":\"to\",\"type\":\"address\"},{\"indexed\":false,\"internalType\":\"uint256\",\"name\":\"value\",\"type\":\"uint256\"}],\"name\":\"Transfer\",\"type\":\"event\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"owner\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"}],\"name\":\"allowance\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"approve\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"account\",\"type\":\"address\"}],\"name\":\"balanceOf\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"decimals\",\"outputs\":[{\"internalType\":\"uint8\",\"name\":\"\",\"type\":\"uint8\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"subtractedValue\",\"type\":\"uint256\"}],\"name\":\"decreaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"getOwner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"spender\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"addedValue\",\"type\":\"uint256\"}],\"name\":\"increaseAllowance\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"msgSender\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"name\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"owner\",\"outputs\":[{\"internalType\":\"address\",\"name\":\"\",\"type\":\"address\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"renounceOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"symbol\",\"outputs\":[{\"internalType\":\"string\",\"name\":\"\",\"type\":\"string\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"totalSupply\",\"outputs\":[{\"internalType\":\"uint256\",\"name\":\"\",\"type\":\"uint256\"}],\"stateMutability\":\"view\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transfer\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"sender\",\"type\":\"address\"},{\"internalType\":\"address\",\"name\":\"recipient\",\"type\":\"address\"},{\"internalType\":\"uint256\",\"name\":\"amount\",\"type\":\"uint256\"}],\"name\":\"transferFrom\",\"outputs\":[{\"internalType\":\"bool\",\"name\":\"\",\"type\":\"bool\"}],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"internalType\":\"address\",\"name\":\"newOwner\",\"type\":\"address\"}],\"name\":\"transferOwnership\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"}]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc20ApprovalEvent {
+    pub owner: crate::web3::types::Address,
+    pub spender: crate::web3::types::Address,
+    pub value: crate::web3::types::U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc20OwnershipTransferredEvent {
+    pub previous_owner: crate::web3::types::Address,
+    pub new_owner: crate::web3::types::Address,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc20TransferEvent {
+    pub from: crate::web3::types::Address,
+    pub to: crate::web3::types::Address,
+    pub value: crate::web3::types::U256,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub trait Erc20ContractAbi: crate::abi::ContractAbi {
+    fn allowance(&self, owner: crate::web3::types::Address, spender: crate::web3::types::Address) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("allowance", &[crate::web3::contract::tokens::Tokenizable::into_token(owner), crate::web3::contract::tokens::Tokenizable::into_token(spender)])
+    }
+
+    fn decode_allowance(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::U256> {
+        let tokens = self.decode_method("allowance", data)?;
+        Ok(<crate::web3::types::U256 as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn approve(&self, spender: crate::web3::types::Address, amount: crate::web3::types::U256) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("approve", &[crate::web3::contract::tokens::Tokenizable::into_token(spender), crate::web3::contract::tokens::Tokenizable::into_token(amount)])
+    }
+
+    fn decode_approve(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<bool> {
+        let tokens = self.decode_method("approve", data)?;
+        Ok(<bool as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn balance_of(&self, account: crate::web3::types::Address) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("balanceOf", &[crate::web3::contract::tokens::Tokenizable::into_token(account)])
+    }
+
+    fn decode_balance_of(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::U256> {
+        let tokens = self.decode_method("balanceOf", data)?;
+        Ok(<crate::web3::types::U256 as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn decimals(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("decimals", &[])
+    }
+
+    fn decode_decimals(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::U256> {
+        let tokens = self.decode_method("decimals", data)?;
+        Ok(<crate::web3::types::U256 as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn decrease_allowance(&self, spender: crate::web3::types::Address, subtracted_value: crate::web3::types::U256) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("decreaseAllowance", &[crate::web3::contract::tokens::Tokenizable::into_token(spender), crate::web3::contract::tokens::Tokenizable::into_token(subtracted_value)])
+    }
+
+    fn decode_decrease_allowance(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<bool> {
+        let tokens = self.decode_method("decreaseAllowance", data)?;
+        Ok(<bool as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn get_owner(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("getOwner", &[])
+    }
+
+    fn decode_get_owner(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::Address> {
+        let tokens = self.decode_method("getOwner", data)?;
+        Ok(<crate::web3::types::Address as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn increase_allowance(&self, spender: crate::web3::types::Address, added_value: crate::web3::types::U256) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("increaseAllowance", &[crate::web3::contract::tokens::Tokenizable::into_token(spender), crate::web3::contract::tokens::Tokenizable::into_token(added_value)])
+    }
+
+    fn decode_increase_allowance(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<bool> {
+        let tokens = self.decode_method("increaseAllowance", data)?;
+        Ok(<bool as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn msg_sender(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("msgSender", &[])
+    }
+
+    fn decode_msg_sender(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::Address> {
+        let tokens = self.decode_method("msgSender", data)?;
+        Ok(<crate::web3::types::Address as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn name(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("name", &[])
+    }
+
+    fn decode_name(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<String> {
+        let tokens = self.decode_method("name", data)?;
+        Ok(<String as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn owner(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("owner", &[])
+    }
+
+    fn decode_owner(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::Address> {
+        let tokens = self.decode_method("owner", data)?;
+        Ok(<crate::web3::types::Address as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn renounce_ownership(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("renounceOwnership", &[])
+    }
+
+    fn decode_renounce_ownership(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<()> {
+        self.decode_method("renounceOwnership", data)?;
+        Ok(())
+    }
+
+    fn symbol(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("symbol", &[])
+    }
+
+    fn decode_symbol(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<String> {
+        let tokens = self.decode_method("symbol", data)?;
+        Ok(<String as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn total_supply(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("totalSupply", &[])
+    }
+
+    fn decode_total_supply(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<crate::web3::types::U256> {
+        let tokens = self.decode_method("totalSupply", data)?;
+        Ok(<crate::web3::types::U256 as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn transfer(&self, recipient: crate::web3::types::Address, amount: crate::web3::types::U256) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("transfer", &[crate::web3::contract::tokens::Tokenizable::into_token(recipient), crate::web3::contract::tokens::Tokenizable::into_token(amount)])
+    }
+
+    fn decode_transfer(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<bool> {
+        let tokens = self.decode_method("transfer", data)?;
+        Ok(<bool as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn transfer_from(&self, sender: crate::web3::types::Address, recipient: crate::web3::types::Address, amount: crate::web3::types::U256) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("transferFrom", &[crate::web3::contract::tokens::Tokenizable::into_token(sender), crate::web3::contract::tokens::Tokenizable::into_token(recipient), crate::web3::contract::tokens::Tokenizable::into_token(amount)])
+    }
+
+    fn decode_transfer_from(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<bool> {
+        let tokens = self.decode_method("transferFrom", data)?;
+        Ok(<bool as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn transfer_ownership(&self, new_owner: crate::web3::types::Address) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("transferOwnership", &[crate::web3::contract::tokens::Tokenizable::into_token(new_owner)])
+    }
+
+    fn decode_transfer_ownership(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<()> {
+        self.decode_method("transferOwnership", data)?;
+        Ok(())
+    }
+
+    fn decode_approval_event(&self, log: &crate::web3::types::Log) -> crate::abi::Result<Erc20ApprovalEvent> {
+        let event = self.abi().event("Approval")?;
+        let log = event.parse_log(crate::web3::ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        let mut params = log.params.into_iter();
+        let mut next = || params.next().map(|p| p.value).ok_or(crate::web3::ethabi::Error::InvalidData);
+        Ok(Erc20ApprovalEvent {
+            owner: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            spender: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            value: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+        })
+    }
+
+    fn decode_ownership_transferred_event(&self, log: &crate::web3::types::Log) -> crate::abi::Result<Erc20OwnershipTransferredEvent> {
+        let event = self.abi().event("OwnershipTransferred")?;
+        let log = event.parse_log(crate::web3::ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        let mut params = log.params.into_iter();
+        let mut next = || params.next().map(|p| p.value).ok_or(crate::web3::ethabi::Error::InvalidData);
+        Ok(Erc20OwnershipTransferredEvent {
+            previous_owner: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            new_owner: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+        })
+    }
+
+    fn decode_transfer_event(&self, log: &crate::web3::types::Log) -> crate::abi::Result<Erc20TransferEvent> {
+        let event = self.abi().event("Transfer")?;
+        let log = event.parse_log(crate::web3::ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        let mut params = log.params.into_iter();
+        let mut next = || params.next().map(|p| p.value).ok_or(crate::web3::ethabi::Error::InvalidData);
+        Ok(Erc20TransferEvent {
+            from: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            to: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            value: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+        })
+    }
+}
+
+pub struct Erc20Abi {
+    abi: crate::web3::ethabi::Contract
+}
+
+impl Erc20Abi {
+    pub fn new() -> Self {
+        Self {
+            abi: crate::web3::ethabi::Contract::load(ERC20_ABI_JSON.as_bytes()).expect("ERC20_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for Erc20Abi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::abi::ContractAbi for Erc20Abi {
+    fn abi(&self) -> &crate::web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl Erc20ContractAbi for Erc20Abi {}
+
+#[allow(clippy::too_many_arguments)]
+#[crate::async_trait::async_trait]
+pub trait Erc20Contract<T: Erc20ContractAbi>: crate::contract::ContractLiving<T> {
+    async fn allowance(&self, owner: crate::web3::types::Address, spender: crate::web3::types::Address) -> crate::contract::Result<crate::web3::types::U256> {
+        let data = Erc20ContractAbi::allowance(self.abi(), owner, spender)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_allowance(data)?)
+    }
+
+    async fn approve(&self, spender: crate::web3::types::Address, amount: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::approve(self.abi(), spender, amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn approve_by_nonce(&self, spender: crate::web3::types::Address, amount: crate::web3::types::U256, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::approve(self.abi(), spender, amount)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn balance_of(&self, account: crate::web3::types::Address) -> crate::contract::Result<crate::web3::types::U256> {
+        let data = Erc20ContractAbi::balance_of(self.abi(), account)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_balance_of(data)?)
+    }
+
+    async fn decimals(&self) -> crate::contract::Result<crate::web3::types::U256> {
+        let data = Erc20ContractAbi::decimals(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_decimals(data)?)
+    }
+
+    async fn decrease_allowance(&self, spender: crate::web3::types::Address, subtracted_value: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::decrease_allowance(self.abi(), spender, subtracted_value)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn decrease_allowance_by_nonce(&self, spender: crate::web3::types::Address, subtracted_value: crate::web3::types::U256, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::decrease_allowance(self.abi(), spender, subtracted_value)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn get_owner(&self) -> crate::contract::Result<crate::web3::types::Address> {
+        let data = Erc20ContractAbi::get_owner(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_get_owner(data)?)
+    }
+
+    async fn increase_allowance(&self, spender: crate::web3::types::Address, added_value: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::increase_allowance(self.abi(), spender, added_value)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn increase_allowance_by_nonce(&self, spender: crate::web3::types::Address, added_value: crate::web3::types::U256, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::increase_allowance(self.abi(), spender, added_value)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn msg_sender(&self) -> crate::contract::Result<crate::web3::types::Address> {
+        let data = Erc20ContractAbi::msg_sender(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_msg_sender(data)?)
+    }
+
+    async fn name(&self) -> crate::contract::Result<String> {
+        let data = Erc20ContractAbi::name(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_name(data)?)
+    }
+
+    async fn owner(&self) -> crate::contract::Result<crate::web3::types::Address> {
+        let data = Erc20ContractAbi::owner(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_owner(data)?)
+    }
+
+    async fn renounce_ownership(&self, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::renounce_ownership(self.abi())?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn renounce_ownership_by_nonce(&self, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::renounce_ownership(self.abi())?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn symbol(&self) -> crate::contract::Result<String> {
+        let data = Erc20ContractAbi::symbol(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_symbol(data)?)
+    }
+
+    async fn total_supply(&self) -> crate::contract::Result<crate::web3::types::U256> {
+        let data = Erc20ContractAbi::total_supply(self.abi())?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_total_supply(data)?)
+    }
+
+    async fn transfer(&self, recipient: crate::web3::types::Address, amount: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer(self.abi(), recipient, amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn transfer_by_nonce(&self, recipient: crate::web3::types::Address, amount: crate::web3::types::U256, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer(self.abi(), recipient, amount)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn transfer_from(&self, sender: crate::web3::types::Address, recipient: crate::web3::types::Address, amount: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer_from(self.abi(), sender, recipient, amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn transfer_from_by_nonce(&self, sender: crate::web3::types::Address, recipient: crate::web3::types::Address, amount: crate::web3::types::U256, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer_from(self.abi(), sender, recipient, amount)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn transfer_ownership(&self, new_owner: crate::web3::types::Address, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer_ownership(self.abi(), new_owner)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn transfer_ownership_by_nonce(&self, new_owner: crate::web3::types::Address, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = Erc20ContractAbi::transfer_ownership(self.abi(), new_owner)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+}
+
+pub struct Erc20 {
+    engine: crate::engine::Engine,
+    contract: crate::web3::types::Address,
+    abi: Erc20Abi,
+}
+
+impl Erc20 {
+    pub fn new(engine: crate::engine::Engine, contract: crate::web3::types::Address) -> Self {
+        Self {engine, contract, abi: Erc20Abi::new()}
+    }
+}
+
+impl crate::contract::ContractLiving<Erc20Abi> for Erc20 {
+    fn engine(&self) -> &crate::engine::Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &crate::web3::types::Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &Erc20Abi {
+        &self.abi
+    }
+}
+
+impl Erc20Contract<Erc20Abi> for Erc20 {}
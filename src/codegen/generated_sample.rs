@@ -0,0 +1,139 @@
+// 由 crate::codegen 生成, 请勿手动修改
+
+pub const SAMPLE_ABI_JSON: &str = "[{\"inputs\":[{\"name\":\"offset\",\"type\":\"int256\"},{\"name\":\"tick\",\"type\":\"int24\"}],\"name\":\"setOffset\",\"outputs\":[],\"stateMutability\":\"nonpayable\",\"type\":\"function\"},{\"inputs\":[{\"name\":\"values\",\"type\":\"int256[]\"}],\"name\":\"sum\",\"outputs\":[{\"name\":\"\",\"type\":\"int256\"},{\"name\":\"\",\"type\":\"int64\"}],\"stateMutability\":\"pure\",\"type\":\"function\"},{\"inputs\":[],\"name\":\"deposit\",\"outputs\":[],\"stateMutability\":\"payable\",\"type\":\"function\"},{\"anonymous\":false,\"inputs\":[],\"name\":\"Paused\",\"type\":\"event\"},{\"anonymous\":false,\"inputs\":[{\"indexed\":true,\"name\":\"offset\",\"type\":\"int256\"},{\"indexed\":false,\"name\":\"tick\",\"type\":\"int24\"}],\"name\":\"OffsetChanged\",\"type\":\"event\"}]";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SampleOffsetChangedEvent {
+    pub offset: crate::web3::ethabi::Int,
+    pub tick: i32,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SamplePausedEvent {
+}
+
+#[allow(clippy::too_many_arguments)]
+pub trait SampleContractAbi: crate::abi::ContractAbi {
+    fn deposit(&self) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("deposit", &[])
+    }
+
+    fn decode_deposit(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<()> {
+        self.decode_method("deposit", data)?;
+        Ok(())
+    }
+
+    fn set_offset(&self, offset: crate::web3::ethabi::Int, tick: i32) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("setOffset", &[crate::web3::ethabi::Token::Int(offset), crate::web3::contract::tokens::Tokenizable::into_token(tick)])
+    }
+
+    fn decode_set_offset(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<()> {
+        self.decode_method("setOffset", data)?;
+        Ok(())
+    }
+
+    fn sum(&self, values: Vec<crate::web3::ethabi::Int>) -> crate::abi::Result<crate::web3::types::Bytes> {
+        self.method("sum", &[crate::web3::ethabi::Token::Array(values.into_iter().map(crate::web3::ethabi::Token::Int).collect())])
+    }
+
+    fn decode_sum(&self, data: crate::web3::types::Bytes) -> crate::abi::Result<(crate::web3::ethabi::Int, i64)> {
+        let tokens = self.decode_method("sum", data)?;
+        Ok(<(crate::web3::ethabi::Int, i64) as crate::web3::contract::tokens::Detokenize>::from_tokens(tokens)?)
+    }
+
+    fn decode_offset_changed_event(&self, log: &crate::web3::types::Log) -> crate::abi::Result<SampleOffsetChangedEvent> {
+        let event = self.abi().event("OffsetChanged")?;
+        let log = event.parse_log(crate::web3::ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        let mut params = log.params.into_iter();
+        let mut next = || params.next().map(|p| p.value).ok_or(crate::web3::ethabi::Error::InvalidData);
+        Ok(SampleOffsetChangedEvent {
+            offset: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+            tick: crate::web3::contract::tokens::Tokenizable::from_token(next()?)?,
+        })
+    }
+
+    fn decode_paused_event(&self, log: &crate::web3::types::Log) -> crate::abi::Result<SamplePausedEvent> {
+        let event = self.abi().event("Paused")?;
+        event.parse_log(crate::web3::ethabi::RawLog { topics: log.topics.clone(), data: log.data.0.clone() })?;
+        Ok(SamplePausedEvent {})
+    }
+}
+
+pub struct SampleAbi {
+    abi: crate::web3::ethabi::Contract
+}
+
+impl SampleAbi {
+    pub fn new() -> Self {
+        Self {
+            abi: crate::web3::ethabi::Contract::load(SAMPLE_ABI_JSON.as_bytes()).expect("SAMPLE_ABI_JSON is error")
+        }
+    }
+}
+
+impl Default for SampleAbi {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl crate::abi::ContractAbi for SampleAbi {
+    fn abi(&self) -> &crate::web3::ethabi::Contract {
+        &self.abi
+    }
+}
+
+impl SampleContractAbi for SampleAbi {}
+
+#[allow(clippy::too_many_arguments)]
+#[crate::async_trait::async_trait]
+pub trait SampleContract<T: SampleContractAbi>: crate::contract::ContractLiving<T> {
+    async fn deposit(&self, value: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = SampleContractAbi::deposit(self.abi())?;
+        Ok(self.send_data_with_value(data, value, private_key).await?)
+    }
+
+    async fn set_offset(&self, offset: crate::web3::ethabi::Int, tick: i32, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = SampleContractAbi::set_offset(self.abi(), offset, tick)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn set_offset_by_nonce(&self, offset: crate::web3::ethabi::Int, tick: i32, nonce: crate::web3::types::U256, private_key: &str) -> crate::contract::Result<(crate::web3::types::H256, crate::tx::Tx)> {
+        let data = SampleContractAbi::set_offset(self.abi(), offset, tick)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn sum(&self, values: Vec<crate::web3::ethabi::Int>) -> crate::contract::Result<(crate::web3::ethabi::Int, i64)> {
+        let data = SampleContractAbi::sum(self.abi(), values)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_sum(data)?)
+    }
+}
+
+pub struct Sample {
+    engine: crate::engine::Engine,
+    contract: crate::web3::types::Address,
+    abi: SampleAbi,
+}
+
+impl Sample {
+    pub fn new(engine: crate::engine::Engine, contract: crate::web3::types::Address) -> Self {
+        Self {engine, contract, abi: SampleAbi::new()}
+    }
+}
+
+impl crate::contract::ContractLiving<SampleAbi> for Sample {
+    fn engine(&self) -> &crate::engine::Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &crate::web3::types::Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &SampleAbi {
+        &self.abi
+    }
+}
+
+impl SampleContract<SampleAbi> for Sample {}
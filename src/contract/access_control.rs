@@ -0,0 +1,103 @@
+use std::collections::{BTreeSet, HashMap};
+use web3::futures::TryStreamExt;
+use web3::types::{Address, FilterBuilder, H256};
+use crate::abi::access_control::{AccessControlAbi, AccessControlContractAbi, RoleEvent};
+use crate::abi::ContractAbi;
+use crate::contract::ContractLiving;
+use crate::engine::ScanConfig;
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+// AccessControl 角色管理, 使用独立的 AccessControlAbi 编码, 适用于任意 ContractLiving 类型
+#[async_trait]
+pub trait AccessControlContract<T>: ContractLiving<T> {
+    async fn has_role(&self, role: H256, account: Address) -> Result<bool> {
+        let abi = AccessControlAbi::new();
+        let data = self.call_data(abi.has_role(role, account)?).await?;
+        Ok(abi.decode_has_role(data)?)
+    }
+
+    async fn get_role_admin(&self, role: H256) -> Result<H256> {
+        let abi = AccessControlAbi::new();
+        let data = self.call_data(abi.get_role_admin(role)?).await?;
+        Ok(abi.decode_get_role_admin(data)?)
+    }
+
+    async fn grant_role(&self, role: H256, account: Address, private_key: &str) -> Result<(H256, Tx)> {
+        let data = AccessControlAbi::new().grant_role(role, account)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn revoke_role(&self, role: H256, account: Address, private_key: &str) -> Result<(H256, Tx)> {
+        let data = AccessControlAbi::new().revoke_role(role, account)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // 放弃私钥对应账户自己的角色
+    async fn renounce_role(&self, role: H256, private_key: &str) -> Result<(H256, Tx)> {
+        let (account, _) = self.engine().parse_private_key_to_address(private_key)?;
+        let data = AccessControlAbi::new().renounce_role(role, account)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // AccessControl 没有枚举成员的方法(AccessControlEnumerable 除外), 通过 RoleGranted/RoleRevoked 日志重建
+    async fn role_members(&self, role: H256, from_block: u64, to_block: u64, config: ScanConfig) -> Result<BTreeSet<Address>> {
+        let abi = AccessControlAbi::new();
+        let filter = FilterBuilder::default()
+            .address(vec![*self.contract()])
+            .topics(
+                Some(vec![abi.event_topic("RoleGranted")?, abi.event_topic("RoleRevoked")?]),
+                Some(vec![role]),
+                None,
+                None,
+            );
+        let logs: Vec<_> = self.engine().scan_logs(filter, from_block, to_block, config).try_collect().await?;
+        let events = logs.iter().filter_map(|log| abi.decode_role_log(log).ok());
+        Ok(role_members(events).remove(&role).unwrap_or_default())
+    }
+}
+
+impl<T, C: ContractLiving<T>> AccessControlContract<T> for C {}
+
+// 按日志顺序应用授权/撤销, 返回每个角色当前的成员
+pub fn role_members<I: IntoIterator<Item = RoleEvent>>(events: I) -> HashMap<H256, BTreeSet<Address>> {
+    let mut members: HashMap<H256, BTreeSet<Address>> = HashMap::new();
+    for event in events {
+        match event {
+            RoleEvent::Granted(e) => {
+                members.entry(e.role).or_default().insert(e.account);
+            }
+            RoleEvent::Revoked(e) => {
+                if let Some(accounts) = members.get_mut(&e.role) {
+                    accounts.remove(&e.account);
+                }
+            }
+        }
+    }
+    members
+}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::Address;
+    use crate::abi::access_control::{role, RoleChangedEvent, RoleEvent, MINTER_ROLE, PAUSER_ROLE};
+    use crate::contract::access_control::role_members;
+
+    #[test]
+    fn access_control_role_members() {
+        let (a, b, sender) = (Address::random(), Address::random(), Address::random());
+        let event = |role, account| RoleChangedEvent { role, account, sender };
+        let members = role_members(vec![
+            RoleEvent::Granted(event(role(MINTER_ROLE), a)),
+            RoleEvent::Granted(event(role(MINTER_ROLE), b)),
+            RoleEvent::Granted(event(role(PAUSER_ROLE), a)),
+            RoleEvent::Revoked(event(role(MINTER_ROLE), a)),
+            // 重复授权和撤销不存在的成员不影响结果
+            RoleEvent::Granted(event(role(MINTER_ROLE), b)),
+            RoleEvent::Revoked(event(role(PAUSER_ROLE), b)),
+        ]);
+        assert_eq!(members[&role(MINTER_ROLE)].iter().collect::<Vec<_>>(), vec![&b]);
+        assert_eq!(members[&role(PAUSER_ROLE)].iter().collect::<Vec<_>>(), vec![&a]);
+    }
+}
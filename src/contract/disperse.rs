@@ -0,0 +1,289 @@
+use std::str::FromStr;
+use web3::types::{Address, Bytes, H256, TransactionReceipt, U256, U64};
+use crate::abi::disperse::{DisperseAbi, DisperseContractAbi};
+use crate::contract::ContractLiving;
+use crate::contract::erc20::{Erc20, Erc20Contract};
+use crate::engine::{Engine};
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+// disperse.app 在主网及多数EVM链上的部署地址
+pub const DISPERSE_ADDRESS: &str = "0xD152f549545093347A162Dce210e7293f1452150";
+
+// max_gas 为每笔交易的gas上限, 超过时减少接收人数量; chunk_size 为每笔交易的初始接收人数量
+#[derive(Debug, Clone, Copy)]
+pub struct DisperseConfig {
+    pub max_gas: U256,
+    pub chunk_size: usize,
+}
+
+impl Default for DisperseConfig {
+    fn default() -> Self {
+        Self { max_gas: U256::from(8_000_000), chunk_size: 500 }
+    }
+}
+
+// 一笔 disperse 交易的结果, 对应接收人列表中的 [start, end)
+#[derive(Debug, Clone, PartialEq)]
+pub struct DisperseChunk {
+    pub start: usize,
+    pub end: usize,
+    pub total: U256,
+    pub gas: U256,
+    pub hash: Option<H256>,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+enum Asset {
+    Ether,
+    Token(Address),
+}
+
+#[async_trait]
+pub trait DisperseContract<T: DisperseContractAbi>: ContractLiving<T> {
+    async fn disperse_ether(&self, recipients: &[(Address, U256)], private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().disperse_ether(recipients)?;
+        Ok(self.send_data_with_value(data, total(recipients), private_key).await?)
+    }
+
+    // 需要先授权本合约
+    async fn disperse_token(&self, token: Address, recipients: &[(Address, U256)], private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().disperse_token(token, recipients)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // 按gas上限拆分为多笔交易依次发送, 单笔失败不影响后续
+    async fn airdrop_ether(&self, recipients: &[(Address, U256)], config: DisperseConfig, private_key: &str) -> Result<Vec<DisperseChunk>> {
+        airdrop(self, Asset::Ether, recipients, config, private_key).await
+    }
+
+    // 授权不足时先授权总额, 再按gas上限拆分发送
+    async fn airdrop_token(&self, token: &Erc20, recipients: &[(Address, U256)], config: DisperseConfig, private_key: &str) -> Result<Vec<DisperseChunk>> {
+        token.ensure_allowance(*self.contract(), total(recipients), private_key).await?;
+        airdrop(self, Asset::Token(*token.contract()), recipients, config, private_key).await
+    }
+}
+
+fn total(recipients: &[(Address, U256)]) -> U256 {
+    recipients.iter().fold(U256::zero(), |sum, (_, value)| sum.saturating_add(*value))
+}
+
+// 估算gas超过上限时, 按比例缩小接收人数量(留10%余量), 至少减少一个
+fn shrink_chunk(size: usize, gas: U256, max_gas: U256) -> usize {
+    if gas.is_zero() {
+        return size;
+    }
+    let scaled: U256 = U256::from(size) * max_gas * 9 / (gas * 10);
+    scaled.min(U256::from(size - 1)).max(U256::one()).as_usize()
+}
+
+// 超过gas上限或请求过大的错误, 减少接收人数量可以解决; 余额不足, revert 等其他错误直接记录
+fn is_size_error(e: &crate::engine::Error) -> bool {
+    let message = match e {
+        crate::engine::Error::ErrWeb3(web3::Error::Rpc(e)) => e.message.to_lowercase(),
+        crate::engine::Error::ErrWeb3(web3::Error::Transport(e)) => format!("{:?}", e).to_lowercase(),
+        _ => return false,
+    };
+    ["gas required exceeds", "exceeds block gas limit", "out of gas", "oversized", "too large", "size exceeds", "413"]
+        .iter()
+        .any(|pattern| message.contains(pattern))
+}
+
+async fn airdrop<T: DisperseContractAbi, C: DisperseContract<T> + ?Sized + Sync>(contract: &C, asset: Asset, recipients: &[(Address, U256)], config: DisperseConfig, private_key: &str) -> Result<Vec<DisperseChunk>> {
+    let engine = contract.engine();
+    let (from, _) = engine.parse_private_key_to_address(private_key)?;
+    let mut chunks = Vec::new();
+    let mut start = 0;
+    let mut size = config.chunk_size.max(1);
+
+    while start < recipients.len() {
+        size = size.min(recipients.len() - start);
+        let slice = &recipients[start..start + size];
+        let (data, value) = match asset {
+            Asset::Ether => (contract.abi().disperse_ether(slice)?, Some(total(slice))),
+            Asset::Token(token) => (contract.abi().disperse_token(token, slice)?, None),
+        };
+        let mut chunk = DisperseChunk { start, end: start + size, total: total(slice), gas: U256::zero(), hash: None, success: false, error: None };
+
+        match engine.estimate_gas(from, Some(*contract.contract()), value, Some(data.clone())).await {
+            Ok(gas) if gas > config.max_gas && size > 1 => {
+                size = shrink_chunk(size, gas, config.max_gas);
+                continue;
+            }
+            Ok(gas) if gas > config.max_gas => {
+                chunk.gas = gas;
+                chunk.error = Some(format!("gas {} exceeds max_gas", gas));
+            }
+            // 接收人太多时部分节点直接返回错误, 减半重试
+            Err(e) if size > 1 && is_size_error(&e) => {
+                size /= 2;
+                continue;
+            }
+            Err(e) => chunk.error = Some(format!("{:?}", e)),
+            Ok(gas) => {
+                chunk.gas = gas;
+                match send_chunk(engine, *contract.contract(), value, data, private_key).await {
+                    Ok((hash, receipt)) => {
+                        chunk.hash = Some(hash);
+                        chunk.success = receipt.status != Some(U64::zero());
+                    }
+                    Err(e) => chunk.error = Some(format!("{:?}", e)),
+                }
+            }
+        }
+        start = chunk.end;
+        chunks.push(chunk);
+        size = config.chunk_size.max(1);
+    }
+    Ok(chunks)
+}
+
+async fn send_chunk(engine: &Engine, contract: Address, value: Option<U256>, data: Bytes, private_key: &str) -> crate::engine::Result<(H256, TransactionReceipt)> {
+    let (hash, _) = match value {
+        Some(value) => engine.send_transaction_by_value_and_data(contract, value, data, private_key).await?,
+        None => engine.send_transaction_by_data(contract, data, private_key).await?,
+    };
+    Ok((hash, engine.wait_transaction(hash).await?))
+}
+
+pub struct Disperse {
+    engine: Engine,
+    contract: Address,
+    abi: DisperseAbi,
+}
+
+impl Disperse {
+    pub fn new(engine: Engine, contract: Address) -> Self {
+        Self {engine, contract, abi: DisperseAbi::new()}
+    }
+
+    pub fn canonical(engine: Engine) -> Self {
+        Self::new(engine, Address::from_str(DISPERSE_ADDRESS).expect("DISPERSE_ADDRESS is error"))
+    }
+
+    pub async fn deploy(engine: Engine, bytecode: Bytes, private_key: &str) -> Result<(Self, TransactionReceipt)> {
+        let abi = DisperseAbi::new();
+        let (contract, receipt) = engine.deploy(&abi, bytecode, &[], private_key).await?;
+        Ok((Self {engine, contract, abi}, receipt))
+    }
+}
+
+impl ContractLiving<DisperseAbi> for Disperse {
+    fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &DisperseAbi {
+        &self.abi
+    }
+}
+
+impl DisperseContract<DisperseAbi> for Disperse {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::{json, Value};
+    use web3::types::{Address, U256};
+    use crate::contract::disperse::{shrink_chunk, Disperse, DisperseConfig, DisperseContract};
+    use crate::contract::erc20::Erc20;
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, rpc_error, MockNode};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn disperse_shrink_chunk() {
+        assert_eq!(shrink_chunk(500, U256::from(20_000_000), U256::from(8_000_000)), 180);
+        assert_eq!(shrink_chunk(10, U256::from(8_000_001), U256::from(8_000_000)), 8);
+        assert_eq!(shrink_chunk(2, U256::from(100_000_000), U256::from(8_000_000)), 1);
+    }
+
+    #[tokio::test]
+    async fn disperse_airdrop() {
+        // eth_call 返回最大值, allowance 足够, 不需要授权
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("{:#x}", U256::MAX)));
+        let node = MockNode::start(responses).await;
+        let engine = Engine::new(node.url).unwrap();
+        let disperse = Disperse::new(engine.clone(), Address::random());
+        let recipients: Vec<_> = (0..5).map(|i| (Address::random(), U256::from(i + 1))).collect();
+        let config = DisperseConfig { chunk_size: 2, ..Default::default() };
+
+        let chunks = disperse.airdrop_ether(&recipients, config, PRIVATE_KEY).await.unwrap();
+        assert_eq!(chunks.iter().map(|c| (c.start, c.end)).collect::<Vec<_>>(), vec![(0, 2), (2, 4), (4, 5)]);
+        assert!(chunks.iter().all(|c| c.success && c.hash.is_some()));
+        assert_eq!(chunks[1].total, U256::from(7));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 3);
+
+        let token = Erc20::new(engine, Address::random());
+        let chunks = disperse.airdrop_token(&token, &recipients, DisperseConfig::default(), PRIVATE_KEY).await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 4);
+
+        // 单个接收人也超过gas上限时记录错误, 不发送交易
+        let config = DisperseConfig { max_gas: U256::from(20_000), chunk_size: 2 };
+        let chunks = disperse.airdrop_ether(&recipients[..2], config, PRIVATE_KEY).await.unwrap();
+        assert_eq!(chunks.len(), 2);
+        assert!(chunks.iter().all(|c| !c.success && c.error.is_some()));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 4);
+    }
+
+    #[tokio::test]
+    async fn disperse_simulation() {
+        // allowance 为0, 先授权再发送, 模拟模式下都不广播
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("0x{:064x}", 1)));
+        let node = MockNode::start_with(responses, |method, params| {
+            let allowance = params[0]["data"].as_str().unwrap_or_default().starts_with("0xdd62ed3e");
+            (method == "eth_call" && allowance).then(|| json!(format!("0x{:064x}", 0)))
+        }).await;
+        let engine = Engine::new(node.url).unwrap().with_simulation(true);
+        let disperse = Disperse::new(engine.clone(), Address::random());
+        let token = Erc20::new(engine, Address::random());
+        let recipients: Vec<_> = (0..3).map(|i| (Address::random(), U256::from(i + 1))).collect();
+        let chunks = disperse.airdrop_token(&token, &recipients, DisperseConfig::default(), PRIVATE_KEY).await.unwrap();
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0].success && chunks[0].hash.is_some());
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+    }
+
+    // disperseEther(address[],uint256[]) 的接收人数量
+    fn recipient_count(params: &Value) -> usize {
+        let data = params[0]["data"].as_str().unwrap_or_default();
+        ((data.len() - 2) / 2 - 4 - 64 - 64) / 64
+    }
+
+    #[tokio::test]
+    async fn disperse_estimate_errors() {
+        // 超过2个接收人时估算返回gas上限错误
+        let node = MockNode::start_with(default_responses(), |method, params| {
+            (method == "eth_estimateGas" && recipient_count(params) > 2).then(|| rpc_error(-32000, "gas required exceeds allowance (30000000)", None))
+        }).await;
+        let disperse = Disperse::new(Engine::new(node.url).unwrap(), Address::random());
+        let recipients: Vec<_> = (0..5).map(|i| (Address::random(), U256::from(i + 1))).collect();
+        let config = DisperseConfig { chunk_size: 4, ..Default::default() };
+        let chunks = disperse.airdrop_ether(&recipients, config, PRIVATE_KEY).await.unwrap();
+        // 每个分组都从 chunk_size 开始尝试: 4 -> 2, 3 -> 1, 2
+        assert_eq!(chunks.iter().map(|c| (c.start, c.end)).collect::<Vec<_>>(), vec![(0, 2), (2, 3), (3, 5)]);
+        assert!(chunks.iter().all(|c| c.success));
+        // 发送交易时会再估算一次
+        assert_eq!(node.params("eth_estimateGas").iter().map(recipient_count).collect::<Vec<_>>(), vec![4, 2, 2, 3, 1, 1, 2, 2]);
+
+        // revert 不是接收人数量的问题, 直接记录错误不拆分
+        let node = MockNode::start_with(default_responses(), |method, _| {
+            (method == "eth_estimateGas").then(|| rpc_error(3, "execution reverted", None))
+        }).await;
+        let disperse = Disperse::new(Engine::new(node.url).unwrap(), Address::random());
+        let chunks = disperse.airdrop_ether(&recipients, config, PRIVATE_KEY).await.unwrap();
+        assert_eq!(chunks.iter().map(|c| (c.start, c.end)).collect::<Vec<_>>(), vec![(0, 4), (4, 5)]);
+        assert!(chunks.iter().all(|c| !c.success && c.error.is_some()));
+        assert_eq!(node.calls("eth_estimateGas"), 2);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+    }
+}
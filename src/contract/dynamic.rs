@@ -0,0 +1,82 @@
+use std::path::Path;
+use serde_json::Value;
+use web3::ethabi::Token;
+use web3::types::{Address, Bytes, H256, TransactionReceipt, U256};
+use crate::abi::ContractAbi;
+use crate::abi::dynamic::DynamicAbi;
+use crate::contract::ContractLiving;
+use crate::engine::{Engine};
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+#[async_trait]
+pub trait DynamicContractCall<T: ContractAbi>: ContractLiving<T> {
+    async fn call(&self, name: &str, tokens: &[Token]) -> Result<Vec<Token>> {
+        let data = self.abi().method(name, tokens)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_method(name, data)?)
+    }
+
+    async fn call_json(&self, name: &str, values: &[Value]) -> Result<Vec<Token>> {
+        let data = self.abi().method_json(name, values)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_method(name, data)?)
+    }
+
+    async fn send(&self, name: &str, tokens: &[Token], private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().method(name, tokens)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn send_by_nonce(&self, name: &str, tokens: &[Token], nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().method(name, tokens)?;
+        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+    }
+
+    async fn send_json(&self, name: &str, values: &[Value], private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().method_json(name, values)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+pub struct DynamicContract {
+    engine: Engine,
+    contract: Address,
+    abi: DynamicAbi,
+}
+
+impl DynamicContract {
+    pub fn new(engine: Engine, contract: Address, abi: DynamicAbi) -> Self {
+        Self {engine, contract, abi}
+    }
+
+    pub fn from_json(engine: Engine, contract: Address, json: &str) -> Result<Self> {
+        Ok(Self::new(engine, contract, DynamicAbi::from_json(json)?))
+    }
+
+    pub fn from_file(engine: Engine, contract: Address, path: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(engine, contract, DynamicAbi::from_file(path)?))
+    }
+
+    pub async fn deploy(engine: Engine, abi: DynamicAbi, bytecode: Bytes, tokens: &[Token], private_key: &str) -> Result<(Self, TransactionReceipt)> {
+        let (contract, receipt) = engine.deploy(&abi, bytecode, tokens, private_key).await?;
+        Ok((Self::new(engine, contract, abi), receipt))
+    }
+}
+
+impl ContractLiving<DynamicAbi> for DynamicContract {
+    fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &DynamicAbi {
+        &self.abi
+    }
+}
+
+impl DynamicContractCall<DynamicAbi> for DynamicContract {}
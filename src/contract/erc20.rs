@@ -1,72 +1,239 @@
-use web3::types::{Address, Bytes, H256, TransactionParameters, U256};
-use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi};
+use web3::contract::tokens::Tokenize;
+use web3::futures::stream::BoxStream;
+use web3::futures::StreamExt;
+use web3::types::{Address, Bytes, FilterBuilder, H256, Log, TransactionReceipt, U256};
+use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi, Erc20Event};
+use crate::abi::multicall::Call3;
+use crate::amount::Amount;
 use crate::contract::ContractLiving;
-use crate::engine::{Engine};
+use crate::contract::multicall::{Multicall, MulticallContract};
+use crate::engine::{CallOptions, Engine, ScanConfig, Simulation};
 use async_trait::async_trait;
 use crate::contract::Result;
 use crate::tx::Tx;
 
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc20Metadata {
+    pub name: String,
+    pub symbol: String,
+    pub decimals: U256,
+    pub total_supply: U256,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Erc20EventLog {
+    pub event: Erc20Event,
+    pub log: Log,
+}
+
 #[async_trait]
 pub trait Erc20Contract<T: Erc20ContractAbi>: ContractLiving<T> {
     async fn name(&self) -> Result<String> {
+        self.name_with_options(CallOptions::default()).await
+    }
+
+    async fn name_with_options(&self, options: CallOptions) -> Result<String> {
         let data = self.abi().name()?;
-        let data = self.call_data(data).await?;
+        let data = self.call_immutable_data_with_options(data, options).await?;
         Ok(self.abi().decode_name(data)?)
     }
 
     async fn symbol(&self) -> Result<String> {
+        self.symbol_with_options(CallOptions::default()).await
+    }
+
+    async fn symbol_with_options(&self, options: CallOptions) -> Result<String> {
         let data = self.abi().symbol()?;
-        let data = self.call_data(data).await?;
-        Ok(self.abi().decode_name(data)?)
+        let data = self.call_immutable_data_with_options(data, options).await?;
+        Ok(self.abi().decode_symbol(data)?)
     }
 
     async fn decimals(&self) -> Result<U256> {
+        self.decimals_with_options(CallOptions::default()).await
+    }
+
+    async fn decimals_with_options(&self, options: CallOptions) -> Result<U256> {
         let data = self.abi().decimals()?;
-        let data = self.call_data(data).await?;
+        let data = self.call_immutable_data_with_options(data, options).await?;
         Ok(self.abi().decode_decimals(data)?)
     }
 
+    // 返回调用者地址, 用于确认 from 选项是否生效
+    async fn msg_sender(&self) -> Result<Address> {
+        self.msg_sender_with_options(CallOptions::default()).await
+    }
+
+    async fn msg_sender_with_options(&self, options: CallOptions) -> Result<Address> {
+        let data = self.abi().msg_sender()?;
+        let data = self.call_data_with_options(data, options).await?;
+        Ok(self.abi().decode_msg_sender(data)?)
+    }
+
     async fn total_supply(&self) -> Result<U256> {
+        self.total_supply_with_options(CallOptions::default()).await
+    }
+
+    async fn total_supply_with_options(&self, options: CallOptions) -> Result<U256> {
         let data = self.abi().total_supply()?;
-        let data = self.call_data(data).await?;
+        let data = self.call_data_with_options(data, options).await?;
         Ok(self.abi().decode_total_supply(data)?)
     }
 
     async fn balance_of(&self, account: Address) -> Result<U256> {
+        self.balance_of_with_options(account, CallOptions::default()).await
+    }
+
+    // 在指定区块(如历史区块)、以指定 from 读取余额
+    async fn balance_of_with_options(&self, account: Address, options: CallOptions) -> Result<U256> {
         let data = self.abi().balance_of(account)?;
-        let data = self.call_data(data).await?;
+        let data = self.call_data_with_options(data, options).await?;
         Ok(self.abi().decode_balance_of(data)?)
     }
 
+    // 通过 Multicall3 一次请求读取 name/symbol/decimals/totalSupply
+    async fn metadata(&self) -> Result<Erc20Metadata> {
+        self.metadata_with_options(CallOptions::default()).await
+    }
+
+    async fn metadata_with_options(&self, options: CallOptions) -> Result<Erc20Metadata> {
+        let contract = *self.contract();
+        let calls = vec![
+            Call3::new(contract, self.abi().name()?),
+            Call3::new(contract, self.abi().symbol()?),
+            Call3::new(contract, self.abi().decimals()?),
+            Call3::new(contract, self.abi().total_supply()?),
+        ];
+        let results = Multicall::canonical(self.engine().clone()).aggregate3_with_options(&calls, options).await?;
+        let mut results = results.into_iter().map(|r| r.return_data);
+        let mut next = || results.next().ok_or(crate::abi::Error::ErrEthAbi(web3::ethabi::Error::InvalidData));
+        Ok(Erc20Metadata {
+            name: self.abi().decode_name(next()?)?,
+            symbol: self.abi().decode_symbol(next()?)?,
+            decimals: self.abi().decode_decimals(next()?)?,
+            total_supply: self.abi().decode_total_supply(next()?)?,
+        })
+    }
+
+    // 通过 Multicall3 批量读取多个地址的余额, 调用失败的地址返回 None
+    async fn balances_of(&self, accounts: &[Address]) -> Result<Vec<Option<U256>>> {
+        self.balances_of_with_options(accounts, CallOptions::default()).await
+    }
+
+    async fn balances_of_with_options(&self, accounts: &[Address], options: CallOptions) -> Result<Vec<Option<U256>>> {
+        let calls = accounts.iter()
+            .map(|a| Ok(Call3::allow_failure(*self.contract(), self.abi().balance_of(*a)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let results = Multicall::canonical(self.engine().clone()).aggregate3_with_options(&calls, options).await?;
+        Ok(results.into_iter().map(|r| r.decode(|d| self.abi().decode_balance_of(d))).collect())
+    }
+
+    // 按代币 decimals() 解析 "1.25" 这样的数量
+    async fn parse_amount(&self, value: &str) -> Result<Amount> {
+        let decimals = Amount::from_decimals(U256::zero(), self.decimals().await?)?.decimals;
+        Ok(Amount::parse(value, decimals)?)
+    }
+
+    async fn balance_of_amount(&self, account: Address) -> Result<Amount> {
+        self.balance_of_amount_with_options(account, CallOptions::default()).await
+    }
+
+    async fn balance_of_amount_with_options(&self, account: Address, options: CallOptions) -> Result<Amount> {
+        let decimals = self.decimals_with_options(options).await?;
+        Ok(Amount::from_decimals(self.balance_of_with_options(account, options).await?, decimals)?)
+    }
+
+    // amount 的精度必须与代币 decimals() 一致
+    async fn transfer_amount(&self, to: Address, amount: Amount, private_key: &str) -> Result<(H256, Tx)> {
+        let decimals = Amount::from_decimals(U256::zero(), self.decimals().await?)?.decimals;
+        if amount.decimals != decimals {
+            return Err(crate::amount::Error::ErrDecimals(amount.decimals, decimals).into());
+        }
+        self.transfer(to, amount.raw, private_key).await
+    }
+
     async fn transfer(&self, to: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
         let data = self.abi().transfer(to, amount)?;
-        Ok(self.send_data(data, private_key).await?)
+        self.send_data_verified(data, None, private_key, |abi, output| abi.decode_transfer(output)).await
+    }
+
+    // 返回 false 的代币也视为失败
+    async fn simulate_transfer(&self, from: Address, to: Address, amount: U256) -> Result<Simulation> {
+        let mut simulation = self.simulate_data(from, self.abi().transfer(to, amount)?, None).await?;
+        if simulation.success {
+            if let Err(e) = self.abi().decode_transfer(simulation.output.clone()) {
+                simulation.success = false;
+                simulation.revert_reason = Some(e.to_string());
+            }
+        }
+        Ok(simulation)
     }
 
     async fn transfer_by_nonce(&self, to: Address, amount: U256, nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
         let data = self.abi().transfer(to, amount)?;
-        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+        self.send_data_verified(data, Some(nonce), private_key, |abi, output| abi.decode_transfer(output)).await
     }
 
     async fn allowance(&self, owner: Address, spender: Address) -> Result<U256> {
+        self.allowance_with_options(owner, spender, CallOptions::default()).await
+    }
+
+    async fn allowance_with_options(&self, owner: Address, spender: Address, options: CallOptions) -> Result<U256> {
         let data = self.abi().allowance(owner,spender)?;
-        let data = self.call_data(data).await?;
+        let data = self.call_data_with_options(data, options).await?;
         Ok(self.abi().decode_allowance(data)?)
     }
 
     async fn approve(&self, spender: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
         let data = self.abi().approve(spender, amount)?;
-        Ok(self.send_data(data, private_key).await?)
+        self.send_data_verified(data, None, private_key, |abi, output| abi.decode_approve(output)).await
+    }
+
+    // 部分代币(如USDT)不允许把非0的授权直接改为另一个非0值, 先归零再授权, 每一步都等待交易确认
+    async fn safe_approve(&self, spender: Address, amount: U256, private_key: &str) -> Result<Vec<TransactionReceipt>> {
+        let (owner, _) = self.engine().parse_private_key_to_address(private_key)?;
+        let current = self.allowance(owner, spender).await?;
+        let mut receipts = Vec::new();
+        if current == amount {
+            return Ok(receipts);
+        }
+        if !current.is_zero() && !amount.is_zero() {
+            let (hash, _) = self.approve(spender, U256::zero(), private_key).await?;
+            receipts.push(self.engine().wait_transaction_success(hash).await?);
+        }
+        let (hash, _) = self.approve(spender, amount, private_key).await?;
+        receipts.push(self.engine().wait_transaction_success(hash).await?);
+        Ok(receipts)
+    }
+
+    // 通过 increaseAllowance/decreaseAllowance 调整到目标授权, 只发送一笔交易, 需要代币支持这两个方法
+    async fn safe_approve_by_delta(&self, spender: Address, amount: U256, private_key: &str) -> Result<Vec<TransactionReceipt>> {
+        let (owner, _) = self.engine().parse_private_key_to_address(private_key)?;
+        let current = self.allowance(owner, spender).await?;
+        let hash = match current.cmp(&amount) {
+            std::cmp::Ordering::Equal => return Ok(Vec::new()),
+            std::cmp::Ordering::Less => self.increase_allowance(spender, amount - current, private_key).await?.0,
+            std::cmp::Ordering::Greater => self.decrease_allowance(spender, current - amount, private_key).await?.0,
+        };
+        Ok(vec![self.engine().wait_transaction_success(hash).await?])
+    }
+
+    // 当前授权不足 min 时才授权为 min
+    async fn ensure_allowance(&self, spender: Address, min: U256, private_key: &str) -> Result<Vec<TransactionReceipt>> {
+        let (owner, _) = self.engine().parse_private_key_to_address(private_key)?;
+        if self.allowance(owner, spender).await? >= min {
+            return Ok(Vec::new());
+        }
+        self.safe_approve(spender, min, private_key).await
     }
 
     async fn transfer_from(&self, from: Address, to: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
         let data = self.abi().transfer_from(from, to, amount)?;
-        Ok(self.send_data(data, private_key).await?)
+        self.send_data_verified(data, None, private_key, |abi, output| abi.decode_transfer_from(output)).await
     }
 
     async fn transfer_from_by_nonce(&self, from: Address, to: Address, amount: U256, nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
         let data = self.abi().transfer_from(from, to, amount)?;
-        Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
+        self.send_data_verified(data, Some(nonce), private_key, |abi, output| abi.decode_transfer_from(output)).await
     }
 
     async fn increase_allowance(&self, spender: Address, added_value: U256, private_key: &str) -> Result<(H256, Tx)> {
@@ -88,6 +255,52 @@ pub trait Erc20Contract<T: Erc20ContractAbi>: ContractLiving<T> {
         let data = self.abi().decrease_allowance(spender, subtracted_value)?;
         Ok(self.send_data_by_nonce(data, nonce, private_key).await?)
     }
+
+    // 只返回本合约发出的事件
+    fn receipt_events(&self, receipt: &TransactionReceipt) -> Vec<Erc20Event> {
+        receipt.logs.iter()
+            .filter(|log| log.address == *self.contract())
+            .filter_map(|log| self.abi().decode_log(log).ok())
+            .collect()
+    }
+
+    // 订阅本合约的新事件, 有WebSocket时使用 eth_subscribe, 否则轮询 eth_getFilterChanges
+    async fn subscribe_events(&self) -> Result<BoxStream<'_, Result<Erc20EventLog>>> {
+        let filter = FilterBuilder::default().address(vec![*self.contract()]).build();
+        Ok(decode_logs(self, self.engine().subscribe_logs(filter).await?))
+    }
+
+    // 订阅转账事件, from/to 为空时不过滤
+    async fn subscribe_transfers(&self, from: Option<Vec<Address>>, to: Option<Vec<Address>>) -> Result<BoxStream<'_, Result<Erc20EventLog>>> {
+        let topic = self.abi().event_topic("Transfer")?;
+        let filter = FilterBuilder::default()
+            .address(vec![*self.contract()])
+            .topics(
+                Some(vec![topic]),
+                from.map(|from| from.into_iter().map(H256::from).collect()),
+                to.map(|to| to.into_iter().map(H256::from).collect()),
+                None,
+            )
+            .build();
+        Ok(decode_logs(self, self.engine().subscribe_logs(filter).await?))
+    }
+
+    // 扫描历史区块中本合约的事件, 无法解析的日志会被跳过
+    fn scan_events(&self, from_block: u64, to_block: u64, config: ScanConfig) -> BoxStream<'_, Result<Erc20EventLog>> where Self: Sync {
+        let filter = FilterBuilder::default().address(vec![*self.contract()]);
+        decode_logs(self, self.engine().scan_logs(filter, from_block, to_block, config))
+    }
+}
+
+// 解析日志为事件, 无法解析的日志(如其他合约的同名事件)会被跳过
+fn decode_logs<'a, T: Erc20ContractAbi, C: Erc20Contract<T> + ?Sized + Sync>(contract: &'a C, logs: BoxStream<'a, crate::engine::Result<Log>>) -> BoxStream<'a, Result<Erc20EventLog>> {
+    logs.filter_map(move |log| async move {
+        match log {
+            Ok(log) => contract.abi().decode_log(&log).ok().map(|event| Ok(Erc20EventLog { event, log })),
+            Err(e) => Some(Err(e.into())),
+        }
+    })
+    .boxed()
 }
 
 pub struct Erc20 {
@@ -100,6 +313,13 @@ impl Erc20 {
     pub fn new(engine: Engine, contract: Address) -> Self {
         Self {engine, contract, abi: Erc20Abi::new()}
     }
+
+    pub async fn deploy(engine: Engine, bytecode: Bytes, name: &str, symbol: &str, private_key: &str) -> Result<(Self, TransactionReceipt)> {
+        let abi = Erc20Abi::new();
+        let tokens = (name.to_string(), symbol.to_string()).into_tokens();
+        let (contract, receipt) = engine.deploy(&abi, bytecode, &tokens, private_key).await?;
+        Ok((Self {engine, contract, abi}, receipt))
+    }
 }
 
 impl ContractLiving<Erc20Abi> for Erc20 {
@@ -121,10 +341,15 @@ impl Erc20Contract<Erc20Abi> for Erc20 {}
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
-    use web3::types::{Address, U256};
+    use serde_json::json;
+    use web3::futures::TryStreamExt;
+    use web3::types::{Address, H256, U256};
+    use crate::abi::ContractAbi;
+    use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi, Erc20Event, TransferEvent};
     use crate::contract::ContractLiving;
     use crate::contract::erc20::{Erc20, Erc20Contract};
-    use crate::engine::Engine;
+    use crate::engine::{BlockTag, CallOptions, Engine};
+    use crate::mock::{default_responses, MockNode};
 
     const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
 
@@ -151,4 +376,153 @@ mod tests {
         assert_eq!(balance_of, U256::exp10(20))
     }
 
-}
\ No newline at end of file
+    async fn allowance_node(allowance: U256) -> MockNode {
+        let mut responses = default_responses();
+        let mut data = [0u8; 32];
+        allowance.to_big_endian(&mut data);
+        responses.insert("eth_call", json!(format!("0x{}", hex::encode(data))));
+        MockNode::start(responses).await
+    }
+
+    #[tokio::test]
+    async fn erc20_call_options() {
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("0x{:064x}", 100)));
+        let node = MockNode::start(responses).await;
+        let from = Address::random();
+        let options = CallOptions::default().from(from).block(BlockTag::Number(5));
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+        assert_eq!(erc20.balance_of_with_options(Address::random(), options).await.unwrap(), U256::from(100));
+        assert_eq!(erc20.allowance_with_options(from, Address::random(), options).await.unwrap(), U256::from(100));
+        assert_eq!(erc20.total_supply_with_options(options).await.unwrap(), U256::from(100));
+        for params in node.params("eth_call") {
+            assert_eq!(params[0]["from"], format!("{:?}", from));
+            assert_eq!(params[1], "0x5");
+        }
+
+        // 常量读取带选项时不使用缓存
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap().with_cache(true), *erc20.contract());
+        erc20.decimals().await.unwrap();
+        erc20.decimals().await.unwrap();
+        assert_eq!(node.calls("eth_call"), 4);
+        assert_eq!(erc20.decimals_with_options(options).await.unwrap(), U256::from(100));
+        assert_eq!(erc20.decimals_with_options(options).await.unwrap(), U256::from(100));
+        assert_eq!(node.calls("eth_call"), 6);
+        let params = node.params("eth_call");
+        assert_eq!(params[3][1], "latest");
+        assert_eq!(params[5][0]["from"], format!("{:?}", from));
+        assert_eq!(params[5][1], "0x5");
+
+        // 不带选项的读取(包括授权前的 allowance 检查)始终在 latest 执行
+        assert_eq!(erc20.balance_of(Address::random()).await.unwrap(), U256::from(100));
+        erc20.ensure_allowance(Address::random(), U256::from(10), PRIVATE_KEY).await.unwrap();
+        for params in &node.params("eth_call")[6..] {
+            assert_eq!(params[0]["from"], serde_json::Value::Null);
+            assert_eq!(params[1], "latest");
+        }
+    }
+
+    #[tokio::test]
+    async fn erc20_msg_sender() {
+        // 与合约一样返回 eth_call 的 from, 没有 from 时为0地址
+        let node = MockNode::start_with(default_responses(), |method, params| {
+            let from = params[0]["from"].as_str().map(|f| f.trim_start_matches("0x").to_string()).unwrap_or_default();
+            (method == "eth_call").then(|| json!(format!("0x{:0>64}", from)))
+        }).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+        let from = Address::random();
+        assert_eq!(erc20.msg_sender().await.unwrap(), Address::zero());
+        assert_eq!(erc20.msg_sender_with_options(CallOptions::default().from(from)).await.unwrap(), from);
+        let params = node.params("eth_call");
+        assert_eq!(params[1][0]["from"], format!("{:?}", from));
+        assert_eq!(params[1][0]["data"], format!("0x{}", hex::encode(&erc20.abi().msg_sender().unwrap().0)));
+    }
+
+    #[tokio::test]
+    async fn erc20_simulate_transfer() {
+        // 返回 false 的代币模拟结果为失败
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("0x{:064x}", 0)));
+        let node = MockNode::start(responses).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+        let simulation = erc20.simulate_transfer(Address::random(), Address::random(), U256::one()).await.unwrap();
+        assert!(!simulation.success);
+        assert_eq!(simulation.revert_reason.as_deref(), Some("transfer returned false"));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+    }
+
+    #[tokio::test]
+    async fn erc20_simulation_returns_false() {
+        let node = allowance_node(U256::zero()).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap().with_simulation(true), Address::random());
+        let to = Address::random();
+        for result in [
+            erc20.transfer(to, U256::one(), PRIVATE_KEY).await,
+            erc20.transfer_by_nonce(to, U256::one(), U256::zero(), PRIVATE_KEY).await,
+            erc20.transfer_from(Address::random(), to, U256::one(), PRIVATE_KEY).await,
+            erc20.approve(to, U256::one(), PRIVATE_KEY).await,
+        ] {
+            assert!(matches!(result, Err(crate::contract::Error::ErrEngine(crate::engine::Error::ErrReverted(ref reason))) if reason.ends_with("returned false")), "{:?}", result);
+        }
+
+        // 返回 true 时得到模拟交易的hash, 不广播
+        let node = allowance_node(U256::one()).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap().with_simulation(true), Address::random());
+        let (hash, tx) = erc20.transfer(to, U256::one(), PRIVATE_KEY).await.unwrap();
+        assert_eq!(tx.hash, Some(hash));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+    }
+
+    #[tokio::test]
+    async fn erc20_safe_approve() {
+        let spender = Address::random();
+
+        // 非0改为非0: 先归零再授权
+        let node = allowance_node(U256::from(5)).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+        assert_eq!(erc20.safe_approve(spender, U256::from(10), PRIVATE_KEY).await.unwrap().len(), 2);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        // 授权已经相同时不发送交易
+        assert!(erc20.safe_approve(spender, U256::from(5), PRIVATE_KEY).await.unwrap().is_empty());
+        assert_eq!(erc20.safe_approve_by_delta(spender, U256::from(3), PRIVATE_KEY).await.unwrap().len(), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 3);
+
+        // 授权足够时不发送交易, 不足时从0直接授权
+        assert!(erc20.ensure_allowance(spender, U256::from(5), PRIVATE_KEY).await.unwrap().is_empty());
+        let node = allowance_node(U256::zero()).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+        assert_eq!(erc20.ensure_allowance(spender, U256::from(5), PRIVATE_KEY).await.unwrap().len(), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+    }
+
+    #[tokio::test]
+    async fn erc20_subscribe_polling() {
+        let (contract, from, to) = (Address::random(), Address::random(), Address::random());
+        let erc20_abi = Erc20Abi::new();
+        let transfer = json!({
+            "address": contract,
+            "topics": [erc20_abi.event_topic("Transfer").unwrap(), H256::from(from), H256::from(to)],
+            "data": format!("0x{:064x}", 100),
+        });
+        // 无法解析的日志被跳过
+        let unknown = json!({"address": contract, "topics": [H256::random()], "data": "0x"});
+        let mut responses = default_responses();
+        responses.insert("eth_newFilter", json!("0x1"));
+        responses.insert("eth_getFilterChanges", json!([unknown, transfer]));
+        let node = MockNode::start(responses).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), contract);
+
+        let mut events = erc20.subscribe_events().await.unwrap();
+        let event = events.try_next().await.unwrap().unwrap();
+        assert!(matches!(event.event, Erc20Event::Transfer(TransferEvent { from: f, to: t, value }) if f == from && t == to && value == U256::from(100)));
+        assert_eq!(event.log.address, contract);
+        drop(events);
+
+        let mut transfers = erc20.subscribe_transfers(None, Some(vec![to])).await.unwrap();
+        assert!(matches!(transfers.try_next().await.unwrap().unwrap().event, Erc20Event::Transfer(_)));
+        let params = node.params("eth_newFilter");
+        assert_eq!(params[0][0]["address"], format!("{:?}", contract));
+        assert_eq!(params[1][0]["topics"], json!([erc20_abi.event_topic("Transfer").unwrap(), null, H256::from(to)]));
+    }
+}
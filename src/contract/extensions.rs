@@ -0,0 +1,191 @@
+// ERC20 可选扩展. 调用前先通过字节码探测合约是否实现了对应函数,
+// 未实现时直接返回 ErrUnsupported, 避免发出一笔必然 revert 的交易.
+// 探测不到的合约(如 Vyper 编译的合约, 非标准代理)可以使用 *_unchecked 方法跳过检查
+use web3::types::{Address, H256, U256};
+use crate::abi::extensions::{BurnableContractAbi, CappedContractAbi, Erc20ExtensionsAbi, MintableContractAbi, PausableContractAbi};
+use crate::contract::{ContractLiving, Error};
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+async fn require_function<T, C: ContractLiving<T> + ?Sized + Sync>(contract: &C, signature: &str) -> Result {
+    if !contract.engine().supports_function(*contract.contract(), signature).await? {
+        return Err(Error::ErrUnsupported(signature.to_string()));
+    }
+    Ok(())
+}
+
+#[async_trait]
+pub trait Erc20Mintable<T>: ContractLiving<T> {
+    async fn supports_mint(&self) -> Result<bool> {
+        Ok(self.engine().supports_function(*self.contract(), "mint(address,uint256)").await?)
+    }
+
+    async fn mint(&self, to: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        require_function(self, "mint(address,uint256)").await?;
+        self.mint_unchecked(to, amount, private_key).await
+    }
+
+    async fn mint_unchecked(&self, to: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        let data = Erc20ExtensionsAbi::new().mint(to, amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+#[async_trait]
+pub trait Erc20Burnable<T>: ContractLiving<T> {
+    async fn supports_burn(&self) -> Result<bool> {
+        Ok(self.engine().supports_function(*self.contract(), "burn(uint256)").await?)
+    }
+
+    async fn burn(&self, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        require_function(self, "burn(uint256)").await?;
+        self.burn_unchecked(amount, private_key).await
+    }
+
+    async fn burn_unchecked(&self, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        let data = Erc20ExtensionsAbi::new().burn(amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // 需要 account 对调用者的授权
+    async fn burn_from(&self, account: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        require_function(self, "burnFrom(address,uint256)").await?;
+        self.burn_from_unchecked(account, amount, private_key).await
+    }
+
+    async fn burn_from_unchecked(&self, account: Address, amount: U256, private_key: &str) -> Result<(H256, Tx)> {
+        let data = Erc20ExtensionsAbi::new().burn_from(account, amount)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+#[async_trait]
+pub trait Erc20Pausable<T>: ContractLiving<T> {
+    async fn supports_pause(&self) -> Result<bool> {
+        Ok(self.engine().supports_function(*self.contract(), "paused()").await?)
+    }
+
+    async fn paused(&self) -> Result<bool> {
+        require_function(self, "paused()").await?;
+        self.paused_unchecked().await
+    }
+
+    async fn paused_unchecked(&self) -> Result<bool> {
+        let abi = Erc20ExtensionsAbi::new();
+        let data = self.call_data(abi.paused()?).await?;
+        Ok(abi.decode_paused(data)?)
+    }
+
+    async fn pause(&self, private_key: &str) -> Result<(H256, Tx)> {
+        require_function(self, "pause()").await?;
+        self.pause_unchecked(private_key).await
+    }
+
+    async fn pause_unchecked(&self, private_key: &str) -> Result<(H256, Tx)> {
+        let data = Erc20ExtensionsAbi::new().pause()?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn unpause(&self, private_key: &str) -> Result<(H256, Tx)> {
+        require_function(self, "unpause()").await?;
+        self.unpause_unchecked(private_key).await
+    }
+
+    async fn unpause_unchecked(&self, private_key: &str) -> Result<(H256, Tx)> {
+        let data = Erc20ExtensionsAbi::new().unpause()?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+#[async_trait]
+pub trait Erc20Capped<T>: ContractLiving<T> {
+    async fn supports_cap(&self) -> Result<bool> {
+        Ok(self.engine().supports_function(*self.contract(), "cap()").await?)
+    }
+
+    // 上限和字节码探测结果不会变化, 开启缓存时只请求一次
+    async fn cap(&self) -> Result<U256> {
+        require_function(self, "cap()").await?;
+        self.cap_unchecked().await
+    }
+
+    async fn cap_unchecked(&self) -> Result<U256> {
+        let abi = Erc20ExtensionsAbi::new();
+        let data = self.call_immutable_data(abi.cap()?).await?;
+        Ok(abi.decode_cap(data)?)
+    }
+}
+
+impl<T, C: ContractLiving<T>> Erc20Mintable<T> for C {}
+
+impl<T, C: ContractLiving<T>> Erc20Burnable<T> for C {}
+
+impl<T, C: ContractLiving<T>> Erc20Pausable<T> for C {}
+
+impl<T, C: ContractLiving<T>> Erc20Capped<T> for C {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use web3::types::{Address, H256, U256};
+    use crate::contract::{ContractLiving, Error};
+    use crate::contract::erc20::Erc20;
+    use crate::contract::extensions::{Erc20Burnable, Erc20Capped, Erc20Mintable, Erc20Pausable};
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, MockNode};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn extensions_probe() {
+        // 只实现了 mint 和 burn 的合约, 不是代理合约
+        let mut responses = default_responses();
+        responses.insert("eth_getCode", json!("0x806340c10f191461011457806342966c6814"));
+        responses.insert("eth_getStorageAt", json!(format!("{:?}", H256::zero())));
+        let node = MockNode::start(responses).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+
+        assert!(erc20.supports_mint().await.unwrap());
+        assert!(!erc20.supports_pause().await.unwrap());
+        erc20.mint(Address::random(), U256::one(), PRIVATE_KEY).await.unwrap();
+        erc20.burn(U256::one(), PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        assert!(matches!(erc20.pause(PRIVATE_KEY).await, Err(Error::ErrUnsupported(s)) if s == "pause()"));
+        assert!(matches!(erc20.burn_from(Address::random(), U256::one(), PRIVATE_KEY).await, Err(Error::ErrUnsupported(_))));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        // 跳过检查直接发送
+        erc20.pause_unchecked(PRIVATE_KEY).await.unwrap();
+        let calls = node.calls("eth_getCode");
+        erc20.burn_from_unchecked(Address::random(), U256::one(), PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_getCode"), calls);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 4);
+    }
+
+    #[tokio::test]
+    async fn extensions_probe_cached() {
+        // 实现了 cap() 的合约
+        let mut responses = default_responses();
+        responses.insert("eth_getCode", json!("0x8063355274ea14"));
+        responses.insert("eth_getStorageAt", json!(format!("{:?}", H256::zero())));
+        responses.insert("eth_call", json!(format!("0x{:064x}", 1000)));
+        let node = MockNode::start(responses).await;
+        let engine = Engine::new(node.url).unwrap().with_cache(true);
+        let erc20 = Erc20::new(engine.clone(), Address::random());
+
+        assert_eq!(erc20.cap().await.unwrap(), U256::from(1000));
+        assert_eq!(erc20.cap().await.unwrap(), U256::from(1000));
+        assert!(!erc20.supports_pause().await.unwrap());
+        assert!(!erc20.supports_pause().await.unwrap());
+        assert_eq!(node.calls("eth_getCode"), 2);
+        assert_eq!(node.calls("eth_call"), 1);
+
+        // 合约升级后清除缓存重新探测
+        engine.invalidate_contract(*erc20.contract());
+        erc20.cap().await.unwrap();
+        assert_eq!(node.calls("eth_getCode"), 3);
+        assert_eq!(node.calls("eth_call"), 2);
+    }
+}
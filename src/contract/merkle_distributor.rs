@@ -0,0 +1,111 @@
+use web3::types::{Address, H256, U256};
+use crate::abi::merkle_distributor::{MerkleDistributorAbi, MerkleDistributorContractAbi};
+use crate::contract::{ContractLiving, Error};
+use crate::engine::Engine;
+use crate::merkle::MerkleTree;
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+#[async_trait]
+pub trait MerkleDistributorContract<T: MerkleDistributorContractAbi>: ContractLiving<T> {
+    async fn token(&self) -> Result<Address> {
+        let data = self.call_immutable_data(self.abi().token()?).await?;
+        Ok(self.abi().decode_token(data)?)
+    }
+
+    async fn merkle_root(&self) -> Result<H256> {
+        let data = self.call_immutable_data(self.abi().merkle_root()?).await?;
+        Ok(self.abi().decode_merkle_root(data)?)
+    }
+
+    async fn is_claimed(&self, index: u64) -> Result<bool> {
+        let data = self.call_data(self.abi().is_claimed(U256::from(index))?).await?;
+        Ok(self.abi().decode_is_claimed(data)?)
+    }
+
+    async fn claim(&self, index: u64, account: Address, amount: U256, proof: &[H256], private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().claim(U256::from(index), account, amount, proof)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // 从本地生成的树中取出 account 的证明, 校验证明、链上 root 和领取状态后再发送, 避免必然 revert 的交易
+    async fn claim_from_tree(&self, tree: &MerkleTree, account: Address, private_key: &str) -> Result<(H256, Tx)> {
+        let (leaf, proof) = tree.proof_of(account).ok_or(Error::ErrInvalidProof(account))?;
+        if !tree.verify(&leaf, &proof) {
+            return Err(Error::ErrInvalidProof(account));
+        }
+        let root = self.merkle_root().await?;
+        if root != tree.root() {
+            return Err(Error::ErrMerkleRoot(root, tree.root()));
+        }
+        if self.is_claimed(leaf.index).await? {
+            return Err(Error::ErrClaimed(leaf.index));
+        }
+        self.claim(leaf.index, leaf.account, leaf.amount, &proof, private_key).await
+    }
+}
+
+pub struct MerkleDistributor {
+    engine: Engine,
+    contract: Address,
+    abi: MerkleDistributorAbi,
+}
+
+impl MerkleDistributor {
+    pub fn new(engine: Engine, contract: Address) -> Self {
+        Self {engine, contract, abi: MerkleDistributorAbi::new()}
+    }
+}
+
+impl ContractLiving<MerkleDistributorAbi> for MerkleDistributor {
+    fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &MerkleDistributorAbi {
+        &self.abi
+    }
+}
+
+impl MerkleDistributorContract<MerkleDistributorAbi> for MerkleDistributor {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use web3::types::{Address, H256, U256};
+    use crate::contract::Error;
+    use crate::contract::merkle_distributor::{MerkleDistributor, MerkleDistributorContract};
+    use crate::engine::Engine;
+    use crate::merkle::{LeafEncoding, MerkleTree};
+    use crate::mock::{default_responses, MockNode};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn merkle_distributor_claim_guard() {
+        let account = Address::random();
+        let tree = MerkleTree::from_balances(&[(account, U256::from(100)), (Address::random(), U256::one())], LeafEncoding::Packed).unwrap();
+        // 链上 root 与本地不一致
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("{:?}", H256::repeat_byte(2))));
+        let node = MockNode::start(responses).await;
+        let distributor = MerkleDistributor::new(Engine::new(node.url).unwrap(), Address::random());
+
+        let result = distributor.claim_from_tree(&tree, Address::random(), PRIVATE_KEY).await;
+        assert!(matches!(result, Err(Error::ErrInvalidProof(_))));
+        assert_eq!(node.calls("eth_call"), 0);
+
+        let result = distributor.claim_from_tree(&tree, account, PRIVATE_KEY).await;
+        assert!(matches!(result, Err(Error::ErrMerkleRoot(root, _)) if root == H256::repeat_byte(2)));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+
+        let (leaf, proof) = tree.proof_of(account).unwrap();
+        distributor.claim(leaf.index, leaf.account, leaf.amount, &proof, PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+    }
+}
@@ -1,8 +1,16 @@
 pub mod erc20;
+pub mod dynamic;
+pub mod multicall;
+pub mod permit;
+pub mod ownable;
+pub mod access_control;
+pub mod extensions;
+pub mod disperse;
+pub mod merkle_distributor;
 
 use thiserror::Error;
-use web3::types::{Address, Bytes, H256, TransactionParameters, U256};
-use crate::engine::{Engine};
+use web3::types::{Address, Bytes, H256, U256};
+use crate::engine::{CallOptions, Engine, Simulation};
 use async_trait::async_trait;
 use crate::tx::Tx;
 
@@ -16,6 +24,24 @@ pub enum Error {
     ErrEngine(#[from] crate::engine::Error),
     #[error("abi error")]
     ErrAbi(#[from] crate::abi::Error),
+    #[error("signing error")]
+    ErrSigning(#[from] web3::signing::SigningError),
+    #[error("amount error")]
+    ErrAmount(#[from] crate::amount::Error),
+    #[error("{0} requires explicit confirmation")]
+    ErrUnconfirmed(String),
+    #[error("{0:?} is not the owner")]
+    ErrNotOwner(Address),
+    #[error("{0:?} is not the pending owner")]
+    ErrNotPendingOwner(Address),
+    #[error("contract does not implement {0}")]
+    ErrUnsupported(String),
+    #[error("merkle root mismatch: contract {0:?}, local {1:?}")]
+    ErrMerkleRoot(H256, H256),
+    #[error("no valid merkle proof for {0:?}")]
+    ErrInvalidProof(Address),
+    #[error("index {0} already claimed")]
+    ErrClaimed(u64),
 }
 
 #[async_trait]
@@ -25,15 +51,64 @@ pub trait ContractLiving<T> {
     fn abi(&self) -> &T;
 
     async fn call_data(&self, data: Bytes) -> Result<Bytes> {
-        Ok(self.engine().call_transaction(self.contract().clone(), data).await?)
+        self.call_data_with_options(data, CallOptions::default()).await
+    }
+
+    // 在指定区块、以指定 from 执行只读调用
+    async fn call_data_with_options(&self, data: Bytes, options: CallOptions) -> Result<Bytes> {
+        Ok(self.engine().call_transaction_with_options(*self.contract(), data, options).await?)
+    }
+
+    // 合约常量(name/symbol/decimals等), Engine 开启缓存时只请求一次
+    async fn call_immutable_data(&self, data: Bytes) -> Result<Bytes> {
+        Ok(self.engine().call_transaction_cached(*self.contract(), data).await?)
+    }
+
+    // 只有 latest 且不指定 from 时才使用缓存, 其他区块或调用者直接请求
+    async fn call_immutable_data_with_options(&self, data: Bytes, options: CallOptions) -> Result<Bytes> {
+        if options == CallOptions::default() {
+            return self.call_immutable_data(data).await;
+        }
+        self.call_data_with_options(data, options).await
+    }
+
+    // 以 from 的身份模拟发送, 不需要私钥
+    async fn simulate_data(&self, from: Address, data: Bytes, value: Option<U256>) -> Result<Simulation> {
+        Ok(self.engine().simulate(from, Some(*self.contract()), value, Some(data)).await?)
     }
 
     async fn send_data(&self, data: Bytes, private_key: &str) -> Result<(H256, Tx)> {
-        Ok(self.engine().send_transaction_by_data(self.contract().clone(), data, private_key).await?)
+        Ok(self.engine().send_transaction_by_data(*self.contract(), data, private_key).await?)
+    }
+
+    async fn send_data_with_value(&self, data: Bytes, value: U256, private_key: &str) -> Result<(H256, Tx)> {
+        Ok(self.engine().send_transaction_by_value_and_data(*self.contract(), value, data, private_key).await?)
     }
 
     async fn send_data_by_nonce(&self, data: Bytes, nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
-        Ok(self.engine().send_transaction_by_data_with_nonce(self.contract().clone(), data, nonce, private_key).await?)
+        Ok(self.engine().send_transaction_by_data_with_nonce(*self.contract(), data, nonce, private_key).await?)
+    }
+
+    // 模拟模式下除了回滚, 还用 verify 检查解码后的返回值(如代币 transfer 返回 false), 不通过时不签名
+    async fn send_data_verified<F>(&self, data: Bytes, nonce: Option<U256>, private_key: &str, verify: F) -> Result<(H256, Tx)>
+        where F: FnOnce(&T, Bytes) -> crate::abi::Result + Send
+    {
+        if !self.engine().is_simulation() {
+            return match nonce {
+                Some(nonce) => self.send_data_by_nonce(data, nonce, private_key).await,
+                None => self.send_data(data, private_key).await,
+            };
+        }
+        let (from, _) = self.engine().parse_private_key_to_address(private_key)?;
+        let simulation = self.simulate_data(from, data.clone(), None).await?;
+        if !simulation.success {
+            return Err(crate::engine::Error::ErrReverted(simulation.revert_reason.unwrap_or_default()).into());
+        }
+        if let Err(e) = verify(self.abi(), simulation.output) {
+            return Err(crate::engine::Error::ErrReverted(e.to_string()).into());
+        }
+        let (raw, tx) = self.engine().sign_transaction(Some(*self.contract()), None, Some(data), nonce, private_key).await?;
+        Ok((self.engine().send_raw_transaction(raw).await?, tx))
     }
 }
 
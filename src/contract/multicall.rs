@@ -0,0 +1,76 @@
+use std::str::FromStr;
+use web3::types::{Address, U256};
+use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi};
+use crate::abi::multicall::{Call3, Call3Result, MulticallAbi, MulticallContractAbi};
+use crate::contract::ContractLiving;
+use crate::engine::{CallOptions, Engine};
+use async_trait::async_trait;
+use crate::contract::Result;
+
+// Multicall3 在各条链上的统一部署地址
+pub const MULTICALL3_ADDRESS: &str = "0xcA11bde05977b3631167028862bE2a173976CA11";
+
+#[async_trait]
+pub trait MulticallContract<T: MulticallContractAbi>: ContractLiving<T> {
+    async fn aggregate3(&self, calls: &[Call3]) -> Result<Vec<Call3Result>> {
+        self.aggregate3_with_options(calls, CallOptions::default()).await
+    }
+
+    async fn aggregate3_with_options(&self, calls: &[Call3], options: CallOptions) -> Result<Vec<Call3Result>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+        let data = self.abi().aggregate3(calls)?;
+        let data = self.call_data_with_options(data, options).await?;
+        Ok(self.abi().decode_aggregate3(data)?)
+    }
+
+    async fn eth_balances(&self, accounts: &[Address]) -> Result<Vec<U256>> {
+        let calls = accounts.iter()
+            .map(|a| Ok(Call3::new(*self.contract(), self.abi().get_eth_balance(*a)?)))
+            .collect::<Result<Vec<_>>>()?;
+        let results = self.aggregate3(&calls).await?;
+        Ok(results.into_iter().map(|r| self.abi().decode_get_eth_balance(r.return_data)).collect::<crate::abi::Result<Vec<_>>>()?)
+    }
+
+    // 多个代币同一持有人的余额, 调用失败的代币返回 None
+    async fn token_balances(&self, tokens: &[Address], holder: Address) -> Result<Vec<Option<U256>>> {
+        let erc20_abi = Erc20Abi::new();
+        let data = erc20_abi.balance_of(holder)?;
+        let calls: Vec<_> = tokens.iter().map(|t| Call3::allow_failure(*t, data.clone())).collect();
+        let results = self.aggregate3(&calls).await?;
+        Ok(results.into_iter().map(|r| r.decode(|d| erc20_abi.decode_balance_of(d))).collect())
+    }
+}
+
+pub struct Multicall {
+    engine: Engine,
+    contract: Address,
+    abi: MulticallAbi,
+}
+
+impl Multicall {
+    pub fn new(engine: Engine, contract: Address) -> Self {
+        Self {engine, contract, abi: MulticallAbi::new()}
+    }
+
+    pub fn canonical(engine: Engine) -> Self {
+        Self::new(engine, Address::from_str(MULTICALL3_ADDRESS).expect("MULTICALL3_ADDRESS is error"))
+    }
+}
+
+impl ContractLiving<MulticallAbi> for Multicall {
+    fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &MulticallAbi {
+        &self.abi
+    }
+}
+
+impl MulticallContract<MulticallAbi> for Multicall {}
@@ -0,0 +1,104 @@
+use web3::types::{Address, H256};
+use crate::abi::ownable::{OwnableAbi, OwnableContractAbi};
+use crate::contract::{ContractLiving, Error};
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+// 放弃所有权前需要显式确认, 防止误操作导致合约永久失去管理员
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenounceOwnership {
+    Confirmed,
+    Unconfirmed,
+}
+
+// Ownable / Ownable2Step 管理, 使用独立的 OwnableAbi 编码, 适用于任意 ContractLiving 类型
+#[async_trait]
+pub trait OwnableContract<T>: ContractLiving<T> {
+    // 先读 owner(), 失败时再尝试 BEP20 的 getOwner()
+    async fn owner(&self) -> Result<Address> {
+        let abi = OwnableAbi::new();
+        match self.call_data(abi.owner()?).await {
+            Ok(data) => Ok(abi.decode_owner(data)?),
+            Err(_) => {
+                let data = self.call_data(abi.get_owner()?).await?;
+                Ok(abi.decode_get_owner(data)?)
+            }
+        }
+    }
+
+    // Ownable2Step 中等待接受的新owner
+    async fn pending_owner(&self) -> Result<Address> {
+        let abi = OwnableAbi::new();
+        let data = self.call_data(abi.pending_owner()?).await?;
+        Ok(abi.decode_pending_owner(data)?)
+    }
+
+    async fn transfer_ownership(&self, new_owner: Address, private_key: &str) -> Result<(H256, Tx)> {
+        let data = OwnableAbi::new().transfer_ownership(new_owner)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    // Ownable2Step: 由 pending_owner 调用
+    async fn accept_ownership(&self, private_key: &str) -> Result<(H256, Tx)> {
+        let (from, _) = self.engine().parse_private_key_to_address(private_key)?;
+        let pending_owner = self.pending_owner().await?;
+        if pending_owner != from {
+            return Err(Error::ErrNotPendingOwner(from));
+        }
+        let data = OwnableAbi::new().accept_ownership()?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+
+    async fn renounce_ownership(&self, confirm: RenounceOwnership, private_key: &str) -> Result<(H256, Tx)> {
+        if confirm != RenounceOwnership::Confirmed {
+            return Err(Error::ErrUnconfirmed("renounceOwnership".to_string()));
+        }
+        let (from, _) = self.engine().parse_private_key_to_address(private_key)?;
+        if self.owner().await? != from {
+            return Err(Error::ErrNotOwner(from));
+        }
+        let data = OwnableAbi::new().renounce_ownership()?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+impl<T, C: ContractLiving<T>> OwnableContract<T> for C {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use web3::types::{Address, H256};
+    use crate::contract::Error;
+    use crate::contract::erc20::Erc20;
+    use crate::contract::ownable::{OwnableContract, RenounceOwnership};
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, MockNode};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn ownable_renounce_guard() {
+        let engine = Engine::new("http://127.0.0.1:8545").unwrap();
+        let (owner, _) = engine.parse_private_key_to_address(PRIVATE_KEY).unwrap();
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("{:?}", H256::from(owner))));
+        let node = MockNode::start(responses).await;
+        let erc20 = Erc20::new(Engine::new(node.url).unwrap(), Address::random());
+
+        assert_eq!(erc20.owner().await.unwrap(), owner);
+        let result = erc20.renounce_ownership(RenounceOwnership::Unconfirmed, PRIVATE_KEY).await;
+        assert!(matches!(result, Err(Error::ErrUnconfirmed(_))));
+        assert_eq!(node.calls("eth_call"), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+
+        erc20.renounce_ownership(RenounceOwnership::Confirmed, PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+
+        // 不是 pending owner 时不发送交易
+        let other = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+        assert!(matches!(erc20.accept_ownership(other).await, Err(Error::ErrNotPendingOwner(_))));
+        assert!(matches!(erc20.renounce_ownership(RenounceOwnership::Confirmed, other).await, Err(Error::ErrNotOwner(_))));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+    }
+}
@@ -0,0 +1,118 @@
+use web3::signing::{Key, SecretKeyRef};
+use web3::types::{Address, H256, U256};
+use crate::abi::permit::{Erc20PermitAbi, Erc20PermitContractAbi, Permit, PermitSignature};
+use crate::contract::ContractLiving;
+use crate::contract::erc20::Erc20Contract;
+use crate::engine::Engine;
+use async_trait::async_trait;
+use crate::contract::Result;
+use crate::tx::Tx;
+
+// EIP-2612: 持有人离线签名授权, 由中继账户提交 permit 交易并支付gas
+#[async_trait]
+pub trait Erc20PermitContract<T: Erc20PermitContractAbi>: Erc20Contract<T> {
+    async fn nonces(&self, owner: Address) -> Result<U256> {
+        let data = self.abi().nonces(owner)?;
+        let data = self.call_data(data).await?;
+        Ok(self.abi().decode_nonces(data)?)
+    }
+
+    async fn domain_separator(&self) -> Result<H256> {
+        let data = self.abi().domain_separator()?;
+        let data = self.call_immutable_data(data).await?;
+        Ok(self.abi().decode_domain_separator(data)?)
+    }
+
+    async fn version(&self) -> Result<String> {
+        let data = self.abi().version()?;
+        let data = self.call_immutable_data(data).await?;
+        Ok(self.abi().decode_version(data)?)
+    }
+
+    // 持有人签名, 返回的 Permit 和签名交给中继账户提交
+    async fn sign_permit(&self, spender: Address, value: U256, deadline: U256, owner_private_key: &str) -> Result<(Permit, PermitSignature)> {
+        let (owner, private_key) = self.engine().parse_private_key_to_address(owner_private_key)?;
+        let permit = Permit {
+            owner,
+            spender,
+            value,
+            nonce: self.nonces(owner).await?,
+            deadline,
+        };
+        let digest = permit.digest(self.domain_separator().await?);
+        let signature = SecretKeyRef::new(&private_key).sign(digest.as_bytes(), None)?;
+        Ok((permit, PermitSignature { v: signature.v as u8, r: signature.r, s: signature.s }))
+    }
+
+    // private_key 为中继账户, 与 permit.owner 无关
+    async fn permit(&self, permit: &Permit, signature: &PermitSignature, private_key: &str) -> Result<(H256, Tx)> {
+        let data = self.abi().permit(permit, signature)?;
+        Ok(self.send_data(data, private_key).await?)
+    }
+}
+
+pub struct Erc20Permit {
+    engine: Engine,
+    contract: Address,
+    abi: Erc20PermitAbi,
+}
+
+impl Erc20Permit {
+    pub fn new(engine: Engine, contract: Address) -> Self {
+        Self {engine, contract, abi: Erc20PermitAbi::new()}
+    }
+}
+
+impl ContractLiving<Erc20PermitAbi> for Erc20Permit {
+    fn engine(&self) -> &Engine {
+        &self.engine
+    }
+
+    fn contract(&self) -> &Address {
+        &self.contract
+    }
+
+    fn abi(&self) -> &Erc20PermitAbi {
+        &self.abi
+    }
+}
+
+impl Erc20Contract<Erc20PermitAbi> for Erc20Permit {}
+
+impl Erc20PermitContract<Erc20PermitAbi> for Erc20Permit {}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use web3::signing::recover;
+    use web3::types::{Address, H256, U256};
+    use crate::contract::permit::{Erc20Permit, Erc20PermitContract};
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, MockNode};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[tokio::test]
+    async fn permit_sign() {
+        // nonces 和 DOMAIN_SEPARATOR 都返回 0x..01
+        let mut responses = default_responses();
+        responses.insert("eth_call", json!(format!("{:?}", H256::from_low_u64_be(1))));
+        let node = MockNode::start(responses).await;
+        let engine = Engine::new(node.url).unwrap();
+        let token = Erc20Permit::new(engine.clone(), Address::random());
+
+        let spender = Address::random();
+        let (permit, signature) = token.sign_permit(spender, U256::exp10(18), U256::MAX, PRIVATE_KEY).await.unwrap();
+        let (owner, _) = engine.parse_private_key_to_address(PRIVATE_KEY).unwrap();
+        assert_eq!(permit.owner, owner);
+        assert_eq!(permit.nonce, U256::one());
+
+        let mut compact = signature.r.as_bytes().to_vec();
+        compact.extend_from_slice(signature.s.as_bytes());
+        let digest = permit.digest(H256::from_low_u64_be(1));
+        assert_eq!(recover(digest.as_bytes(), &compact, signature.v as i32 - 27).unwrap(), owner);
+
+        token.permit(&permit, &signature, PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+    }
+}
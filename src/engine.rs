@@ -1,16 +1,42 @@
-use std::ops::{Add, Mul};
+use std::collections::HashMap;
 use std::str::FromStr;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use secp256k1::SecretKey;
-use web3::signing::{Key, SecretKeyRef};
-use web3::transports::{Http};
-use web3::types::{Address, H256, U256, U64, Bytes, CallRequest, TransactionParameters, TransactionReceipt, AccessList, SignedTransaction};
+use web3::signing::{keccak256, Key, SecretKeyRef};
+use web3::transports::{Batch, Http, WebSocket};
+use web3::futures::future::BoxFuture;
+use web3::futures::stream::{self, BoxStream};
+use web3::futures::{FutureExt, StreamExt, TryStreamExt};
+use web3::types::{Address, H256, U256, U64, Bytes, Block, BlockHeader, BlockId, BlockNumber, CallRequest, Filter, FilterBuilder, Log, TransactionParameters, TransactionReceipt, SignedTransaction};
 use thiserror::Error;
-use web3::Web3;
+use web3::{Transport, Web3};
+use web3::ethabi::{ParamType, Token};
+use crate::abi::ContractAbi;
 use crate::tx::Tx;
+use crate::utils::{code_has_selector, create2_address_from_code, create_address, minimal_proxy_target, selector};
+use crate::utils::units::{parse_units, Unit, Units};
 
 pub type Result<T = ()> = std::result::Result<T, Error>;
 
+// 通用的CREATE2工厂合约(Arachnid deterministic-deployment-proxy), calldata为 salt ++ init_code
+pub const CREATE2_FACTORY: &str = "0x4e59b44847b379578588920cA78FbF26c0B4956C";
+
+// EIP-1967 代理合约保存实现合约地址的slot: keccak256("eip1967.proxy.implementation") - 1
+pub const EIP1967_IMPLEMENTATION_SLOT: &str = "0x360894a13ba1a3210667c828492db98dca3e2076cc3735a920a3ca505d382bbc";
+
+// EIP-1967 beacon 代理保存 beacon 合约地址的slot: keccak256("eip1967.proxy.beacon") - 1
+pub const EIP1967_BEACON_SLOT: &str = "0xa3f0ad74e5423aebfd80d3ef4346578335a9a72aeaee59ff6cb3582b35133d50";
+
+// supports_function 解析代理合约的最大层数
+const MAX_PROXY_DEPTH: usize = 3;
+
+// 默认 gas price, 单位 gwei
+const DEFAULT_GAS_PRICE: &str = "5";
+
+// 没有WebSocket连接时, 通过 eth_getFilterChanges 轮询的间隔
+const FILTER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("web3 error")]
@@ -19,14 +45,143 @@ pub enum Error {
     ErrSecp256k1(#[from] secp256k1::Error),
     #[error("from hex error")]
     ErrHexDecode(#[from] hex::FromHexError),
+    #[error("abi error")]
+    ErrAbi(#[from] crate::abi::Error),
+    #[error("units error")]
+    ErrUnits(#[from] crate::utils::units::Error),
+    #[error("deploy failed: {0:?}")]
+    ErrDeploy(H256),
+    #[error("transaction failed: {0:?}")]
+    ErrTransactionFailed(H256),
+    #[error("simulation reverted: {0}")]
+    ErrReverted(String),
+}
+
+// 交易模拟结果, 成功时 output 为返回值, revert 时为 revert data
+#[derive(Debug, Clone, PartialEq)]
+pub struct Simulation {
+    pub success: bool,
+    pub output: Bytes,
+    // eth_estimateGas 在 pending 区块上的结果, 是执行所需gas上限的估算, 不是实际消耗的gas.
+    // 估算时已 revert 则为 None
+    pub gas_estimate: Option<U256>,
+    pub revert_reason: Option<String>,
+}
+
+impl Simulation {
+    // 节点返回的错误(revert)转换为失败的模拟结果, 网络等其他错误直接返回
+    fn reverted(err: web3::Error) -> Result<Self> {
+        let rpc = match err {
+            web3::Error::Rpc(rpc) => rpc,
+            err => return Err(err.into()),
+        };
+        // 多数节点把 revert data 放在 error.data, 部分节点再包一层 {"data": "0x.."}
+        let data = rpc.data.as_ref()
+            .and_then(|d| d.as_str().or_else(|| d.get("data").and_then(|d| d.as_str())))
+            .and_then(|d| hex::decode(d.trim_start_matches("0x")).ok())
+            .unwrap_or_default();
+        let reason = decode_revert(&data).unwrap_or(rpc.message);
+        Ok(Self { success: false, output: Bytes(data), gas_estimate: None, revert_reason: Some(reason) })
+    }
+}
+
+// 解析 Error(string) 和 Panic(uint256), 自定义错误返回选择器
+pub fn decode_revert(data: &[u8]) -> Option<String> {
+    if data.len() < 4 {
+        return None;
+    }
+    let (selector, args) = data.split_at(4);
+    match selector {
+        [0x08, 0xc3, 0x79, 0xa0] => match web3::ethabi::decode(&[ParamType::String], args).ok()?.pop()? {
+            Token::String(reason) => Some(reason),
+            _ => None,
+        },
+        [0x4e, 0x48, 0x7b, 0x71] => match web3::ethabi::decode(&[ParamType::Uint(256)], args).ok()?.pop()? {
+            Token::Uint(code) => Some(format!("panic {:#04x}", code)),
+            _ => None,
+        },
+        _ => Some(format!("custom error 0x{}", hex::encode(selector))),
+    }
+}
+
+
+// 日志扫描配置, chunk_size 为每次 eth_getLogs 的区块数量, 节点返回结果过多时会自动减半
+#[derive(Debug, Clone, Copy)]
+pub struct ScanConfig {
+    pub chunk_size: u64,
+    pub concurrency: usize,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self { chunk_size: 2000, concurrency: 4 }
+    }
+}
+
+// eth_call 的区块参数, safe/finalized 需要节点支持合并后的标签, Hash 按 EIP-1898 传递
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlockTag {
+    #[default]
+    Latest,
+    Pending,
+    Safe,
+    Finalized,
+    Earliest,
+    Number(u64),
+    Hash(H256),
+}
+
+impl BlockTag {
+    fn to_value(self) -> serde_json::Value {
+        match self {
+            BlockTag::Latest => "latest".into(),
+            BlockTag::Pending => "pending".into(),
+            BlockTag::Safe => "safe".into(),
+            BlockTag::Finalized => "finalized".into(),
+            BlockTag::Earliest => "earliest".into(),
+            BlockTag::Number(n) => format!("{:#x}", n).into(),
+            BlockTag::Hash(hash) => serde_json::json!({ "blockHash": hash }),
+        }
+    }
+}
+
+// 只读调用的选项, from 影响依赖 msg.sender 的函数
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CallOptions {
+    pub from: Option<Address>,
+    pub block: BlockTag,
+}
+
+impl CallOptions {
+    pub fn from(mut self, from: Address) -> Self {
+        self.from = Some(from);
+        self
+    }
+
+    pub fn block(mut self, block: BlockTag) -> Self {
+        self.block = block;
+        self
+    }
 }
 
+// 不会变化的链上数据缓存: chain id, 合约的只读常量(如ERC20的 name/symbol/decimals)
+// 以及字节码探测的结果. Engine clone 后共享同一个缓存
+#[derive(Debug, Default)]
+struct Cache {
+    chain_id: Mutex<Option<U256>>,
+    calls: Mutex<HashMap<(Address, Vec<u8>), Bytes>>,
+    functions: Mutex<HashMap<(Address, [u8; 4]), bool>>,
+}
 
 #[derive(Debug, Clone)]
 pub struct Engine {
     web3: Web3<Http>,
+    ws: Option<Web3<WebSocket>>,
     transaction_type: Option<U64>,
-    gas_price: Option<U256>
+    gas_price: Option<U256>,
+    batch: bool,
+    cache: Option<Arc<Cache>>,
+    simulation: bool,
 }
 
 impl Engine {
@@ -36,8 +191,12 @@ impl Engine {
         let w3 = Web3::new(client);
         Ok(Self{
             web3: w3,
+            ws: None,
             transaction_type: Some(U64::from(2)),
-            gas_price: Some(U256::from(5).mul(U256::exp10(9)))
+            gas_price: Some(parse_units(DEFAULT_GAS_PRICE, Unit::Gwei)?),
+            batch: false,
+            cache: None,
+            simulation: false,
         })
     }
 
@@ -46,15 +205,109 @@ impl Engine {
         let w3 = Web3::new(client);
         Ok(Self{
             web3: w3,
+            ws: None,
             transaction_type: Some(U64::from(t)),
-            gas_price: Some(U256::from(5).mul(U256::exp10(9)))
+            gas_price: Some(parse_units(DEFAULT_GAS_PRICE, Unit::Gwei)?),
+            batch: false,
+            cache: None,
+            simulation: false,
         })
     }
 
+    // 订阅使用WebSocket连接, 其余请求仍然走HTTP
+    pub async fn with_ws(mut self, ws: &str) -> Result<Self> {
+        let client = WebSocket::new(ws).await?;
+        self.ws = Some(Web3::new(client));
+        Ok(self)
+    }
+
+    // 例如 with_gas_price("1.5", "gwei")
+    pub fn with_gas_price<U: Units>(mut self, gas_price: &str, unit: U) -> Result<Self> {
+        self.gas_price = Some(parse_units(gas_price, unit)?);
+        Ok(self)
+    }
+
+    pub fn gas_price(&self) -> Option<U256> {
+        self.gas_price
+    }
+
+    // 发送交易前的 estimateGas / chainId / nonce 合并为一个JSON-RPC batch请求
+    pub fn with_batch(mut self, batch: bool) -> Self {
+        self.batch = batch;
+        self
+    }
+
+    // 缓存 chain id 和合约常量, 默认关闭
+    pub fn with_cache(mut self, cache: bool) -> Self {
+        self.cache = if cache { Some(Arc::new(Cache::default())) } else { None };
+        self
+    }
+
+    // 开启后所有发送交易的方法只签名和模拟, 不广播; revert 时返回 ErrReverted
+    pub fn with_simulation(mut self, simulation: bool) -> Self {
+        self.simulation = simulation;
+        self
+    }
+
+    pub fn is_simulation(&self) -> bool {
+        self.simulation
+    }
+
+    pub fn invalidate_chain_id(&self) {
+        if let Some(cache) = &self.cache {
+            *cache.chain_id.lock().unwrap() = None;
+        }
+    }
+
+    // 清除某个合约缓存的常量, 例如合约升级后
+    pub fn invalidate_contract(&self, contract: Address) {
+        if let Some(cache) = &self.cache {
+            cache.calls.lock().unwrap().retain(|(address, _), _| *address != contract);
+            cache.functions.lock().unwrap().retain(|(address, _), _| *address != contract);
+        }
+    }
+
+    pub fn clear_cache(&self) {
+        if let Some(cache) = &self.cache {
+            *cache.chain_id.lock().unwrap() = None;
+            cache.calls.lock().unwrap().clear();
+            cache.functions.lock().unwrap().clear();
+        }
+    }
+
+    fn cached_chain_id(&self) -> Option<U256> {
+        self.cache.as_ref().and_then(|cache| *cache.chain_id.lock().unwrap())
+    }
+
+    fn cache_chain_id(&self, chain_id: U256) {
+        if let Some(cache) = &self.cache {
+            *cache.chain_id.lock().unwrap() = Some(chain_id);
+        }
+    }
+
+    pub async fn chain_id(&self) -> Result<U256> {
+        if let Some(chain_id) = self.cached_chain_id() {
+            return Ok(chain_id);
+        }
+        let chain_id = self.web3.eth().chain_id().await?;
+        self.cache_chain_id(chain_id);
+        Ok(chain_id)
+    }
+
     pub fn web3(&self) -> &Web3<Http> {
         &self.web3
     }
 
+    // 在返回的 Web3 上发起的请求会先排队, 调用 submit_batch 后一次性发送
+    pub fn batch(&self) -> Web3<Batch<Http>> {
+        Web3::new(Batch::new(self.web3.transport().clone()))
+    }
+
+    pub async fn submit_batch(&self, batch: &Web3<Batch<Http>>) -> Result {
+        batch.transport().submit_batch().await?;
+        Ok(())
+    }
+
     pub fn parse_private_key_to_address(&self, private_key: &str) -> Result<(Address, SecretKey)> {
         let private_key = if private_key.contains("0x") {
             &private_key[2..]
@@ -66,45 +319,83 @@ impl Engine {
         Ok((from, private_key))
     }
 
-    async fn make_transaction(&self, from: Address, to: Address, value: Option<U256>, data: Option<Bytes>, nonce: Option<U256>) -> Result<TransactionParameters> {
-        let transaction_type = self.transaction_type.clone();
-
-        let gas_price = self.gas_price.clone();
-
-        let gas = self.web3.eth().estimate_gas(CallRequest{
+    fn transaction_request(&self, from: Address, to: Option<Address>, value: Option<U256>, data: Option<Bytes>) -> CallRequest {
+        CallRequest{
             from: Some(from),
-            to: Some(to),
+            to,
             gas: None,
-            gas_price,
+            gas_price: self.gas_price,
             value,
-            data: data.clone(),
-            transaction_type,
+            data,
+            transaction_type: self.transaction_type,
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None
-        }, None).await?;
+        }
+    }
 
-        let chain_id = self.web3.eth().chain_id().await?;
+    pub async fn estimate_gas(&self, from: Address, to: Option<Address>, value: Option<U256>, data: Option<Bytes>) -> Result<U256> {
+        Ok(self.web3.eth().estimate_gas(self.transaction_request(from, to, value, data), None).await?)
+    }
+
+    // 以 from 的身份在 pending 区块上估算gas, 再用估算的gas和当前 gas price 执行 eth_call
+    pub async fn simulate(&self, from: Address, to: Option<Address>, value: Option<U256>, data: Option<Bytes>) -> Result<Simulation> {
+        let mut request = self.transaction_request(from, to, value, data);
+        let gas = match self.web3.eth().estimate_gas(request.clone(), Some(BlockNumber::Pending)).await {
+            Ok(gas) => gas,
+            Err(e) => return Simulation::reverted(e),
+        };
+        request.gas = Some(gas);
+        match self.web3.eth().call(request, Some(BlockId::Number(BlockNumber::Pending))).await {
+            Ok(output) => Ok(Simulation { success: true, output, gas_estimate: Some(gas), revert_reason: None }),
+            Err(e) => Simulation::reverted(e).map(|s| Simulation { gas_estimate: Some(gas), ..s }),
+        }
+    }
+
+    async fn make_transaction(&self, from: Address, to: Option<Address>, value: Option<U256>, data: Option<Bytes>, nonce: Option<U256>) -> Result<TransactionParameters> {
+        let transaction_type = self.transaction_type;
+
+        let gas_price = self.gas_price;
+
+        let request = self.transaction_request(from, to, value, data.clone());
+
+        let (gas, chain_id, nonce) = if self.batch {
+            let batch = self.batch();
+            let gas = batch.eth().estimate_gas(request, None);
+            let cached = self.cached_chain_id();
+            let chain_id = if cached.is_none() { Some(batch.eth().chain_id()) } else { None };
+            let count = if nonce.is_none() { Some(batch.eth().transaction_count(from, None)) } else { None };
+            self.submit_batch(&batch).await?;
+            let chain_id = match chain_id {
+                Some(chain_id) => {
+                    let chain_id = chain_id.await?;
+                    self.cache_chain_id(chain_id);
+                    chain_id
+                },
+                None => cached.unwrap_or_default(),
+            };
+            let nonce = match count {
+                Some(count) => count.await?,
+                None => nonce.unwrap_or_default(),
+            };
+            (gas.await?, chain_id, nonce)
+        } else {
+            let gas = self.web3.eth().estimate_gas(request, None).await?;
+            let chain_id = self.chain_id().await?;
+            let nonce = match nonce {
+                Some(t) => t,
+                None => self.web3.eth().transaction_count(from, None).await?,
+            };
+            (gas, chain_id, nonce)
+        };
 
         Ok(TransactionParameters{
-            to: Some(to),
+            to,
             gas,
             gas_price,
-            value: match value {
-                Some(t) => t,
-                None => U256::zero(),
-            },
-            data: match data {
-                Some(t) => t,
-                None => Bytes::default(),
-            },
-            nonce: match nonce {
-                Some(t) => Some(t),
-                None => {
-                    let transaction_count = self.web3.eth().transaction_count(from, None).await?;
-                    Some(transaction_count)
-                },
-            },
+            value: value.unwrap_or_default(),
+            data: data.unwrap_or_default(),
+            nonce: Some(nonce),
             transaction_type,
             access_list: None,
             max_fee_per_gas: None,
@@ -120,47 +411,290 @@ impl Engine {
         tx
     }
 
-    async fn send_transaction(&self, to: Address, value: Option<U256>, data: Option<Bytes>, nonce: Option<U256>, private_key: &str) -> Result<(H256, Tx)> {
+    // 只签名不发送, 返回的原始交易可以之后通过 send_raw_transaction 广播, 交易hash在 Tx.hash 中
+    pub async fn sign_transaction(&self, to: Option<Address>, value: Option<U256>, data: Option<Bytes>, nonce: Option<U256>, private_key: &str) -> Result<(Bytes, Tx)> {
         let (from, private_key) = self.parse_private_key_to_address(private_key)?;
         let tx = self.make_transaction(from, to, value, data, nonce).await?;
         let signed = self.web3.accounts().sign_transaction(tx.clone(), &private_key).await?;
-        let result = self.web3.eth().send_raw_transaction(signed.clone().raw_transaction).await?;
-        Ok((result, self.to_tx(from, tx, signed)))
+        Ok((signed.raw_transaction.clone(), self.to_tx(from, tx, signed)))
+    }
+
+    // 模拟模式下不广播, 返回原始交易的hash
+    pub async fn send_raw_transaction(&self, raw: Bytes) -> Result<H256> {
+        if self.simulation {
+            return Ok(H256::from(keccak256(&raw.0)));
+        }
+        Ok(self.web3.eth().send_raw_transaction(raw).await?)
+    }
+
+    // 包含交易池中未确认交易的 nonce
+    pub async fn pending_nonce(&self, address: Address) -> Result<U256> {
+        Ok(self.web3.eth().transaction_count(address, Some(BlockNumber::Pending)).await?)
+    }
+
+    async fn send_transaction(&self, to: Option<Address>, value: Option<U256>, data: Option<Bytes>, nonce: Option<U256>, private_key: &str) -> Result<(H256, Tx)> {
+        if self.simulation {
+            let (from, _) = self.parse_private_key_to_address(private_key)?;
+            let simulation = self.simulate(from, to, value, data.clone()).await?;
+            if !simulation.success {
+                return Err(Error::ErrReverted(simulation.revert_reason.unwrap_or_default()));
+            }
+        }
+        let (raw, tx) = self.sign_transaction(to, value, data, nonce, private_key).await?;
+        let result = self.send_raw_transaction(raw).await?;
+        Ok((result, tx))
     }
 
     pub async fn send_transaction_by_value(&self, to: Address, value: U256, private_key: &str) -> Result<(H256, Tx)> {
-        self.send_transaction(to, Some(value), None, None, private_key).await
+        self.send_transaction(Some(to), Some(value), None, None, private_key).await
     }
 
     pub async fn send_transaction_by_value_with_nonce(&self, to: Address, value: U256, nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
-        self.send_transaction(to, Some(value), None, Some(nonce), private_key).await
+        self.send_transaction(Some(to), Some(value), None, Some(nonce), private_key).await
     }
 
     pub async fn send_transaction_by_data(&self, to: Address, data: Bytes, private_key: &str) -> Result<(H256, Tx)> {
-        self.send_transaction(to, None, Some(data), None, private_key).await
+        self.send_transaction(Some(to), None, Some(data), None, private_key).await
     }
 
     pub async fn send_transaction_by_data_with_nonce(&self, to: Address, data: Bytes, nonce: U256, private_key: &str) -> Result<(H256, Tx)> {
-        self.send_transaction(to, None, Some(data), Some(nonce), private_key).await
+        self.send_transaction(Some(to), None, Some(data), Some(nonce), private_key).await
     }
 
-    pub async fn call_transaction(&self, contract: Address, data: Bytes) -> Result<Bytes>{
-        let data = self.web3.eth().call(CallRequest{
+    pub async fn send_transaction_by_value_and_data(&self, to: Address, value: U256, data: Bytes, private_key: &str) -> Result<(H256, Tx)> {
+        self.send_transaction(Some(to), Some(value), Some(data), None, private_key).await
+    }
+
+    // 部署合约, 构造参数按照abi中的constructor编码, 等待交易确认后返回合约地址
+    pub async fn deploy<T: ContractAbi + Sync>(&self, abi: &T, bytecode: Bytes, tokens: &[Token], private_key: &str) -> Result<(Address, TransactionReceipt)> {
+        let data = abi.constructor(bytecode, tokens)?;
+        let (hash, tx) = self.send_transaction(None, None, Some(data), None, private_key).await?;
+        let mut receipt = self.wait_transaction(hash).await?;
+        if self.simulation {
+            receipt.contract_address = tx.from.zip(tx.nonce).map(|(from, nonce)| create_address(from, nonce));
+        }
+        match receipt.contract_address {
+            Some(address) if receipt.status != Some(U64::zero()) => Ok((address, receipt)),
+            _ => Err(Error::ErrDeploy(hash)),
+        }
+    }
+
+    // 通过CREATE2工厂部署合约, 预测地址上已有代码时直接返回, 不再发送交易
+    pub async fn deploy_create2<T: ContractAbi + Sync>(&self, abi: &T, bytecode: Bytes, tokens: &[Token], salt: H256, private_key: &str) -> Result<(Address, Option<TransactionReceipt>)> {
+        let factory = Address::from_str(CREATE2_FACTORY).expect("CREATE2_FACTORY is error");
+        let init_code = abi.constructor(bytecode, tokens)?;
+        let address = create2_address_from_code(factory, salt, &init_code.0);
+        if self.has_code(address).await? {
+            return Ok((address, None));
+        }
+
+        let mut data = salt.as_bytes().to_vec();
+        data.extend(init_code.0);
+        let (hash, _) = self.send_transaction_by_data(factory, Bytes(data), private_key).await?;
+        let receipt = self.wait_transaction(hash).await?;
+        if receipt.status == Some(U64::zero()) || (!self.simulation && !self.has_code(address).await?) {
+            return Err(Error::ErrDeploy(hash));
+        }
+        Ok((address, Some(receipt)))
+    }
+
+    pub async fn has_code(&self, address: Address) -> Result<bool> {
+        let code = self.web3.eth().code(address, None).await?;
+        Ok(!code.0.is_empty())
+    }
+
+    // 代理合约的实现地址, 不是代理合约时返回 None. 依次识别 EIP-1167 最小代理(克隆合约),
+    // EIP-1967 implementation slot 和 EIP-1967 beacon slot(实现地址为 beacon 的 implementation())
+    pub async fn proxy_implementation(&self, address: Address) -> Result<Option<Address>> {
+        let code = self.web3.eth().code(address, None).await?;
+        self.resolve_proxy(address, &code.0).await
+    }
+
+    async fn resolve_proxy(&self, address: Address, code: &[u8]) -> Result<Option<Address>> {
+        if let Some(implementation) = minimal_proxy_target(code) {
+            return Ok(Some(implementation));
+        }
+        let slot = U256::from_str(EIP1967_IMPLEMENTATION_SLOT).expect("EIP1967_IMPLEMENTATION_SLOT is error");
+        let implementation = Address::from(self.web3.eth().storage(address, slot, None).await?);
+        if !implementation.is_zero() {
+            return Ok(Some(implementation));
+        }
+        let slot = U256::from_str(EIP1967_BEACON_SLOT).expect("EIP1967_BEACON_SLOT is error");
+        let beacon = Address::from(self.web3.eth().storage(address, slot, None).await?);
+        if beacon.is_zero() {
+            return Ok(None);
+        }
+        let data = self.call_transaction(beacon, Bytes(selector("implementation()").to_vec())).await?;
+        if data.0.len() < 32 {
+            return Ok(None);
+        }
+        let implementation = Address::from_slice(&data.0[12..32]);
+        Ok(if implementation.is_zero() { None } else { Some(implementation) })
+    }
+
+    // 通过字节码中的函数选择器判断合约是否实现了某个函数, 代理合约检查实现合约的字节码.
+    // 克隆合约指向的也可能是代理合约, 最多解析 MAX_PROXY_DEPTH 层. 开启缓存时每个合约的同一函数只探测一次
+    pub async fn supports_function(&self, address: Address, signature: &str) -> Result<bool> {
+        let selector = selector(signature);
+        let key = (address, selector);
+        if let Some(supported) = self.cache.as_ref().and_then(|cache| cache.functions.lock().unwrap().get(&key).copied()) {
+            return Ok(supported);
+        }
+        let supported = self.probe_function(address, selector).await?;
+        if let Some(cache) = &self.cache {
+            cache.functions.lock().unwrap().insert(key, supported);
+        }
+        Ok(supported)
+    }
+
+    async fn probe_function(&self, address: Address, selector: [u8; 4]) -> Result<bool> {
+        let mut address = address;
+        for _ in 0..=MAX_PROXY_DEPTH {
+            let code = self.web3.eth().code(address, None).await?;
+            if code_has_selector(&code.0, selector) {
+                return Ok(true);
+            }
+            match self.resolve_proxy(address, &code.0).await? {
+                Some(implementation) => address = implementation,
+                None => return Ok(false),
+            }
+        }
+        Ok(false)
+    }
+
+    fn call_request(&self, contract: Address, data: Bytes) -> CallRequest {
+        CallRequest{
             from: None,
             to: Some(contract),
             gas: None,
             gas_price: None,
             value: None,
             data: Some(data),
-            transaction_type: self.transaction_type.clone(),
+            transaction_type: self.transaction_type,
             access_list: None,
             max_fee_per_gas: None,
             max_priority_fee_per_gas: None
-        }, None).await?;
-        Ok(data)
+        }
+    }
+
+    pub async fn call_transaction(&self, contract: Address, data: Bytes) -> Result<Bytes>{
+        self.call_transaction_with_options(contract, data, CallOptions::default()).await
+    }
+
+    // web3 的 BlockNumber 不支持 safe/finalized, 直接发送 eth_call
+    pub async fn call_transaction_with_options(&self, contract: Address, data: Bytes, options: CallOptions) -> Result<Bytes> {
+        let mut request = self.call_request(contract, data);
+        request.from = options.from;
+        let params = vec![web3::helpers::serialize(&request), options.block.to_value()];
+        let result = self.web3.transport().execute("eth_call", params).await?;
+        Ok(serde_json::from_value(result).map_err(|e| web3::Error::Decoder(e.to_string()))?)
+    }
+
+    // 只用于返回值不会变化的调用, 开启缓存时每个合约的同一calldata只请求一次
+    pub async fn call_transaction_cached(&self, contract: Address, data: Bytes) -> Result<Bytes> {
+        let cache = match &self.cache {
+            Some(cache) => cache,
+            None => return self.call_transaction(contract, data).await,
+        };
+        let key = (contract, data.0.clone());
+        if let Some(result) = cache.calls.lock().unwrap().get(&key) {
+            return Ok(result.clone());
+        }
+        let result = self.call_transaction(contract, data).await?;
+        cache.calls.lock().unwrap().insert(key, result.clone());
+        Ok(result)
+    }
+
+    // 多个 eth_call 合并为一个batch请求, 每个调用单独返回结果
+    pub async fn call_transactions(&self, calls: Vec<(Address, Bytes)>) -> Result<Vec<Result<Bytes>>> {
+        let batch = self.batch();
+        let futures: Vec<_> = calls.into_iter()
+            .map(|(contract, data)| batch.eth().call(self.call_request(contract, data), None))
+            .collect();
+        self.submit_batch(&batch).await?;
+        let mut results = Vec::with_capacity(futures.len());
+        for f in futures {
+            results.push(f.await.map_err(Error::from));
+        }
+        Ok(results)
+    }
+
+    pub async fn block_number(&self) -> Result<u64> {
+        Ok(self.web3.eth().block_number().await?.as_u64())
+    }
+
+    // 按区块范围分段并发扫描日志, 按 (区块号, 日志序号) 顺序返回
+    pub fn scan_logs(&self, filter: FilterBuilder, from_block: u64, to_block: u64, config: ScanConfig) -> BoxStream<'static, Result<Log>> {
+        let engine = self.clone();
+        stream::iter(chunk_ranges(from_block, to_block, config.chunk_size))
+            .map(move |(start, end)| {
+                let engine = engine.clone();
+                let filter = filter.clone();
+                async move { engine.logs_in_range(filter, start, end).await }
+            })
+            .buffered(config.concurrency.max(1))
+            .map_ok(|logs| stream::iter(logs.into_iter().map(Ok)))
+            .try_flatten()
+            .boxed()
     }
 
+    fn logs_in_range(&self, filter: FilterBuilder, from_block: u64, to_block: u64) -> BoxFuture<'_, Result<Vec<Log>>> {
+        async move {
+            let f = filter.clone()
+                .from_block(BlockNumber::Number(from_block.into()))
+                .to_block(BlockNumber::Number(to_block.into()))
+                .build();
+            match self.web3.eth().logs(f).await {
+                Ok(mut logs) => {
+                    logs.sort_by_key(|log| (log.block_number, log.log_index));
+                    Ok(logs)
+                }
+                Err(web3::Error::Rpc(e)) if from_block < to_block && is_range_error(&e.message) => {
+                    let mid = from_block + (to_block - from_block) / 2;
+                    let mut logs = self.logs_in_range(filter.clone(), from_block, mid).await?;
+                    logs.extend(self.logs_in_range(filter, mid + 1, to_block).await?);
+                    Ok(logs)
+                }
+                Err(e) => Err(e.into()),
+            }
+        }.boxed()
+    }
+
+    pub async fn subscribe_new_heads(&self) -> Result<BoxStream<'static, Result<BlockHeader>>> {
+        if let Some(ws) = &self.ws {
+            let stream = ws.eth_subscribe().subscribe_new_heads().await?;
+            return Ok(stream.map_err(Error::from).boxed());
+        }
+        let web3 = self.web3.clone();
+        Ok(poll_filter::<H256>(self.web3.transport().clone(), "eth_newBlockFilter", vec![]).await?
+            .try_filter_map(move |hash| {
+                let web3 = web3.clone();
+                async move { Ok(web3.eth().block(BlockId::Hash(hash)).await?.map(block_header)) }
+            })
+            .boxed())
+    }
+
+    pub async fn subscribe_pending_transactions(&self) -> Result<BoxStream<'static, Result<H256>>> {
+        if let Some(ws) = &self.ws {
+            let stream = ws.eth_subscribe().subscribe_new_pending_transactions().await?;
+            return Ok(stream.map_err(Error::from).boxed());
+        }
+        poll_filter(self.web3.transport().clone(), "eth_newPendingTransactionFilter", vec![]).await
+    }
+
+    pub async fn subscribe_logs(&self, filter: Filter) -> Result<BoxStream<'static, Result<Log>>> {
+        if let Some(ws) = &self.ws {
+            let stream = ws.eth_subscribe().subscribe_logs(filter).await?;
+            return Ok(stream.map_err(Error::from).boxed());
+        }
+        poll_filter(self.web3.transport().clone(), "eth_newFilter", vec![serde_json::to_value(filter).unwrap()]).await
+    }
+
+    // 模拟模式下交易没有广播, 返回 status = 1 的回执, 发送后等待确认的流程可以照常执行
     pub async fn wait_transaction(&self, hash: H256) -> Result<TransactionReceipt> {
+        if self.simulation {
+            return Ok(TransactionReceipt { transaction_hash: hash, status: Some(U64::one()), ..Default::default() });
+        }
         loop {
             match self.web3().eth().transaction_receipt(hash).await? {
                 Some(receipt) => {
@@ -173,17 +707,114 @@ impl Engine {
             }
         }
     }
+
+    // 等待交易确认, 交易执行失败(status = 0)时返回错误
+    pub async fn wait_transaction_success(&self, hash: H256) -> Result<TransactionReceipt> {
+        let receipt = self.wait_transaction(hash).await?;
+        if receipt.status == Some(U64::zero()) {
+            return Err(Error::ErrTransactionFailed(hash));
+        }
+        Ok(receipt)
+    }
+}
+
+fn block_header(block: Block<H256>) -> BlockHeader {
+    BlockHeader {
+        hash: block.hash,
+        parent_hash: block.parent_hash,
+        uncles_hash: block.uncles_hash,
+        author: block.author,
+        state_root: block.state_root,
+        transactions_root: block.transactions_root,
+        receipts_root: block.receipts_root,
+        number: block.number,
+        gas_used: block.gas_used,
+        gas_limit: block.gas_limit,
+        base_fee_per_gas: block.base_fee_per_gas,
+        extra_data: block.extra_data,
+        logs_bloom: block.logs_bloom.unwrap_or_default(),
+        timestamp: block.timestamp,
+        difficulty: block.difficulty,
+        mix_hash: block.mix_hash,
+        nonce: block.nonce,
+    }
+}
+
+// 没有WebSocket时通过过滤器轮询. 节点重启或过滤器长时间未轮询会被节点删除,
+// eth_getFilterChanges 返回 filter not found 时用相同参数重新创建, 期间的事件会丢失
+async fn poll_filter<T>(transport: Http, method: &'static str, params: Vec<serde_json::Value>) -> Result<BoxStream<'static, Result<T>>>
+where T: serde::de::DeserializeOwned + Send + 'static {
+    let id = transport.execute(method, params.clone()).await?;
+    let state = (transport, id, std::collections::VecDeque::new(), false);
+    Ok(stream::unfold(state, move |(transport, mut id, mut pending, mut polled)| {
+        let params = params.clone();
+        async move {
+            loop {
+                if let Some(item) = pending.pop_front() {
+                    return Some((Ok(item), (transport, id, pending, polled)));
+                }
+                if polled {
+                    tokio::time::sleep(FILTER_POLL_INTERVAL).await;
+                }
+                polled = true;
+                let result = match transport.execute("eth_getFilterChanges", vec![id.clone()]).await {
+                    Ok(changes) => serde_json::from_value::<Vec<T>>(changes)
+                        .map(|items| pending.extend(items))
+                        .map_err(|e| web3::Error::Decoder(e.to_string())),
+                    Err(web3::Error::Rpc(e)) if e.message.to_lowercase().contains("filter not found") => {
+                        transport.execute(method, params.clone()).await.map(|new_id| id = new_id)
+                    }
+                    Err(e) => Err(e),
+                };
+                if let Err(e) = result {
+                    return Some((Err(e.into()), (transport, id, pending, polled)));
+                }
+            }
+        }
+    }).boxed())
+}
+
+fn chunk_ranges(from_block: u64, to_block: u64, chunk_size: u64) -> Vec<(u64, u64)> {
+    let chunk_size = chunk_size.max(1);
+    let mut ranges = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+        let end = to_block.min(start.saturating_add(chunk_size - 1));
+        ranges.push((start, end));
+        if end == u64::MAX {
+            break;
+        }
+        start = end + 1;
+    }
+    ranges
+}
+
+// 各节点对 eth_getLogs 范围/结果数量限制的报错信息.
+// 不能匹配 "rate limit exceeded" 这类限流错误, 否则拆分区间会成倍增加请求
+fn is_range_error(message: &str) -> bool {
+    let message = message.to_lowercase();
+    ["returned more than", "too many logs", "too many results", "block range", "range is too", "range too large", "response size"]
+        .iter()
+        .any(|m| message.contains(m))
 }
 
 #[cfg(test)]
 mod tests {
-    use std::ops::Div;
     use std::str::FromStr;
-    use web3::types::{Address, TransactionParameters, U256};
+    use web3::types::{Address, H256, TransactionParameters, U256};
     use secp256k1::{SecretKey};
     use web3::signing::{Key, SecretKeyRef};
-    use crate::engine::Engine;
-    use crate::utils::ToHex;
+    use web3::types::Bytes;
+    use crate::amount::Amount;
+    use serde_json::{json, Value};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use web3::futures::{StreamExt, TryStreamExt};
+    use web3::types::{Block, FilterBuilder, U64};
+    use web3::signing::keccak256;
+    use crate::engine::{chunk_ranges, decode_revert, is_range_error, BlockTag, CallOptions, Engine, Error, ScanConfig, EIP1967_BEACON_SLOT, EIP1967_IMPLEMENTATION_SLOT};
+    use crate::mock::{default_responses, rpc_error, MockNode};
+    use crate::utils::{create_address, ToHex};
 
     const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
 
@@ -235,7 +866,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn send_transaction_by_value() -> () {
+    async fn send_transaction_by_value() {
         let e = Engine::new("http://127.0.0.1:8545").unwrap();
         let to = Address::from_str("0x7EB8f3364B5F2BDe169b198d2DB41903f575522a").unwrap();
         let hash = e.send_transaction_by_value(
@@ -249,7 +880,7 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn send_transaction_by_value_with_nonce() -> () {
+    async fn send_transaction_by_value_with_nonce() {
         let e = Engine::new("http://127.0.0.1:8545").unwrap();
         let to = Address::from_str("0x7EB8f3364B5F2BDe169b198d2DB41903f575522a").unwrap();
 
@@ -278,7 +909,294 @@ mod tests {
         let e = Engine::new("http://127.0.0.1:8545").unwrap();
         let address = Address::from_str("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199").unwrap();
         let balance = e.web3().eth().balance(address, None).await.unwrap();
-        println!("{}",balance.div(U256::exp10(18)));
+        println!("{}",Amount::new(balance, 18));
+
+    }
+
+    #[test]
+    fn scan_chunk_ranges() {
+        assert_eq!(chunk_ranges(0, 4999, 2000), vec![(0, 1999), (2000, 3999), (4000, 4999)]);
+        assert_eq!(chunk_ranges(10, 10, 2000), vec![(10, 10)]);
+        assert!(chunk_ranges(11, 10, 2000).is_empty());
+        assert_eq!(chunk_ranges(0, 2, 0), vec![(0, 0), (1, 1), (2, 2)]);
+    }
+
+    #[test]
+    fn scan_range_error() {
+        assert!(is_range_error("query returned more than 10000 results"));
+        assert!(is_range_error("eth_getLogs block range is too wide"));
+        assert!(is_range_error("exceed maximum block range: 5000"));
+        assert!(is_range_error("Log response size exceeded."));
+        assert!(is_range_error("block range too large"));
+        assert!(!is_range_error("execution reverted"));
+        assert!(!is_range_error("rate limit exceeded"));
+        assert!(!is_range_error("daily request count exceeded, request rate limited"));
+        assert!(!is_range_error("Too Many Requests"));
+    }
+
+    // 区间超过10个区块时返回错误, 否则每个区块返回一条日志
+    fn limited_logs(method: &str, params: &Value) -> Option<Value> {
+        if method != "eth_getLogs" {
+            return None;
+        }
+        let block = |key: &str| u64::from_str_radix(params[0][key].as_str().unwrap().trim_start_matches("0x"), 16).unwrap();
+        let (from, to) = (block("fromBlock"), block("toBlock"));
+        if to - from + 1 > 10 {
+            return Some(rpc_error(-32005, "query returned more than 10000 results", None));
+        }
+        let logs: Vec<Value> = (from..=to).rev()
+            .map(|n| json!({"address": format!("{:?}", Address::zero()), "topics": [], "data": "0x", "blockNumber": format!("{:#x}", n), "logIndex": "0x0"}))
+            .collect();
+        Some(json!(logs))
+    }
+
+    #[tokio::test]
+    async fn scan_logs_bisect() {
+        let node = MockNode::start_with(default_responses(), limited_logs).await;
+        let e = Engine::new(node.url).unwrap();
+        let config = ScanConfig { chunk_size: 40, concurrency: 1 };
+        let logs: Vec<_> = e.scan_logs(FilterBuilder::default(), 0, 39, config).try_collect().await.unwrap();
+        let blocks: Vec<u64> = logs.iter().map(|log| log.block_number.unwrap().as_u64()).collect();
+        assert_eq!(blocks, (0..40).collect::<Vec<_>>());
+        // 40 -> 20 + 20 -> 4 * 10
+        assert_eq!(node.calls("eth_getLogs"), 7);
+
+        // 限流错误不拆分区间
+        let node = MockNode::start_with(default_responses(), |method, _| {
+            (method == "eth_getLogs").then(|| rpc_error(-32005, "rate limit exceeded", None))
+        }).await;
+        let e = Engine::new(node.url).unwrap();
+        let result: Result<Vec<_>, _> = e.scan_logs(FilterBuilder::default(), 0, 39, config).try_collect().await;
+        assert!(result.is_err());
+        assert_eq!(node.calls("eth_getLogs"), 1);
+    }
+
+    #[tokio::test]
+    async fn subscribe_polling() {
+        let head = Block::<H256> { hash: Some(H256::repeat_byte(2)), number: Some(U64::from(5)), ..Default::default() };
+        let mut responses = default_responses();
+        responses.insert("eth_newBlockFilter", json!("0x1"));
+        responses.insert("eth_newPendingTransactionFilter", json!("0x2"));
+        responses.insert("eth_getBlockByHash", serde_json::to_value(&head).unwrap());
+        let node = MockNode::start_with(responses, |method, params| match (method, params[0].as_str()) {
+            ("eth_getFilterChanges", Some("0x1")) => Some(json!([H256::repeat_byte(2)])),
+            ("eth_getFilterChanges", Some("0x2")) => Some(json!([H256::repeat_byte(3), H256::repeat_byte(4)])),
+            _ => None,
+        }).await;
+        let e = Engine::new(node.url).unwrap();
+
+        let mut heads = e.subscribe_new_heads().await.unwrap();
+        let header = heads.try_next().await.unwrap().unwrap();
+        assert_eq!(header.hash, head.hash);
+        assert_eq!(header.number, head.number);
+        assert_eq!(node.params("eth_getBlockByHash")[0][0], format!("{:?}", H256::repeat_byte(2)));
+
+        let pending: Vec<H256> = e.subscribe_pending_transactions().await.unwrap().take(2).try_collect().await.unwrap();
+        assert_eq!(pending, vec![H256::repeat_byte(3), H256::repeat_byte(4)]);
+        assert_eq!(node.calls("eth_getFilterChanges"), 2);
+    }
+
+    #[tokio::test]
+    async fn subscribe_logs_recreate_filter() {
+        let created = Arc::new(AtomicUsize::new(0));
+        let polled = Arc::new(AtomicUsize::new(0));
+        let log = |n: u64| json!({"address": Address::zero(), "topics": [], "data": "0x", "blockNumber": format!("{:#x}", n)});
+        let node = MockNode::start_with(default_responses(), move |method, params| match method {
+            "eth_newFilter" => Some(json!(format!("{:#x}", created.fetch_add(1, Ordering::SeqCst) + 1))),
+            // 第一个过滤器返回一次结果后被节点删除
+            "eth_getFilterChanges" if params[0] == "0x1" => match polled.fetch_add(1, Ordering::SeqCst) {
+                0 => Some(json!([log(1)])),
+                _ => Some(rpc_error(-32000, "filter not found", None)),
+            },
+            "eth_getFilterChanges" => Some(json!([log(2)])),
+            _ => None,
+        }).await;
+        let e = Engine::new(node.url).unwrap();
+        let filter = FilterBuilder::default().address(vec![Address::repeat_byte(1)]).build();
+        let logs: Vec<_> = e.subscribe_logs(filter).await.unwrap().take(2).try_collect().await.unwrap();
+        let blocks: Vec<u64> = logs.iter().map(|log| log.block_number.unwrap().as_u64()).collect();
+        assert_eq!(blocks, vec![1, 2]);
+
+        // 用相同的条件重新创建
+        let params = node.params("eth_newFilter");
+        assert_eq!(params.len(), 2);
+        assert_eq!(params[0], params[1]);
+        assert_eq!(params[0][0]["address"], format!("{:?}", Address::repeat_byte(1)));
+        assert_eq!(node.params("eth_getFilterChanges").last().unwrap()[0], "0x2");
+    }
+
+    #[tokio::test]
+    async fn proxy_resolution() {
+        for (name, slot) in [("eip1967.proxy.implementation", EIP1967_IMPLEMENTATION_SLOT), ("eip1967.proxy.beacon", EIP1967_BEACON_SLOT)] {
+            assert_eq!(U256::from(keccak256(name.as_bytes())) - 1, U256::from_str(slot).unwrap());
+        }
+
+        // clone -> beacon_proxy -(beacon)-> token, proxy -> token
+        let [clone, beacon_proxy, beacon, proxy, token] = [1u8, 2, 3, 4, 5].map(Address::repeat_byte);
+        let node = MockNode::start_with(default_responses(), move |method, params| {
+            let address = |value: &Value| Address::from_str(value.as_str().unwrap()).unwrap();
+            match method {
+                "eth_getCode" => Some(json!(match address(&params[0]) {
+                    a if a == clone => format!("0x363d3d373d3d3d363d73{}5af43d82803e903d91602b57fd5bf3", hex::encode(beacon_proxy)),
+                    a if a == token => "0x806340c10f1914".to_string(),
+                    _ => "0x00".to_string(),
+                })),
+                "eth_getStorageAt" => {
+                    let slot = U256::from_str(params[1].as_str().unwrap()).unwrap();
+                    let value = match address(&params[0]) {
+                        a if a == proxy && slot == U256::from_str(EIP1967_IMPLEMENTATION_SLOT).unwrap() => H256::from(token),
+                        a if a == beacon_proxy && slot == U256::from_str(EIP1967_BEACON_SLOT).unwrap() => H256::from(beacon),
+                        _ => H256::zero(),
+                    };
+                    Some(json!(value))
+                }
+                "eth_call" if address(&params[0]["to"]) == beacon => Some(json!(H256::from(token))),
+                _ => None,
+            }
+        }).await;
+        let e = Engine::new(node.url).unwrap();
+        assert_eq!(e.proxy_implementation(clone).await.unwrap(), Some(beacon_proxy));
+        assert_eq!(e.proxy_implementation(beacon_proxy).await.unwrap(), Some(token));
+        assert_eq!(e.proxy_implementation(proxy).await.unwrap(), Some(token));
+        assert_eq!(e.proxy_implementation(token).await.unwrap(), None);
+        assert_eq!(node.params("eth_call")[0][0]["data"], "0x5c60da1b");
 
+        assert!(e.supports_function(clone, "mint(address,uint256)").await.unwrap());
+        assert!(e.supports_function(proxy, "mint(address,uint256)").await.unwrap());
+        assert!(!e.supports_function(clone, "pause()").await.unwrap());
     }
-}
\ No newline at end of file
+
+    async fn mock_node() -> MockNode {
+        MockNode::start(default_responses()).await
+    }
+
+    #[tokio::test]
+    async fn batch_make_transaction() {
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap().with_batch(true);
+        let to = Address::random();
+        let (_, tx) = e.send_transaction_by_value(to, U256::exp10(15), PRIVATE_KEY).await.unwrap();
+        assert_eq!(tx.nonce, Some(U256::from(7)));
+        assert_eq!(tx.chain_id, Some(1337));
+        // estimateGas/chainId/getTransactionCount 一个batch + sendRawTransaction
+        assert_eq!(node.http_requests(), 2);
+        assert_eq!(node.calls("eth_getTransactionCount"), 1);
+
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap();
+        e.send_transaction_by_value(to, U256::exp10(15), PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.http_requests(), 4);
+    }
+
+    #[tokio::test]
+    async fn batch_call_transactions() {
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap();
+        let calls = vec![(Address::random(), Bytes(vec![1])), (Address::random(), Bytes(vec![2]))];
+        let results = e.call_transactions(calls).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap(), &Bytes(vec![1]));
+        assert_eq!(node.http_requests(), 1);
+        assert_eq!(node.calls("eth_call"), 2);
+    }
+
+    #[tokio::test]
+    async fn cache_chain_id() {
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap().with_cache(true);
+        let to = Address::random();
+        for _ in 0..3 {
+            e.send_transaction_by_value(to, U256::exp10(15), PRIVATE_KEY).await.unwrap();
+        }
+        assert_eq!(node.calls("eth_chainId"), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 3);
+
+        e.invalidate_chain_id();
+        e.clone().with_batch(true).send_transaction_by_value(to, U256::exp10(15), PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_chainId"), 2);
+
+        let contract = Address::random();
+        for _ in 0..2 {
+            assert_eq!(e.call_transaction_cached(contract, Bytes(vec![1])).await.unwrap(), Bytes(vec![1]));
+        }
+        assert_eq!(node.calls("eth_call"), 1);
+        e.invalidate_contract(contract);
+        e.call_transaction_cached(contract, Bytes(vec![1])).await.unwrap();
+        assert_eq!(node.calls("eth_call"), 2);
+    }
+
+    #[test]
+    fn engine_gas_price() {
+        let e = Engine::new("http://127.0.0.1:8545").unwrap();
+        assert_eq!(e.gas_price(), Some(U256::exp10(9) * 5));
+        let e = e.with_gas_price("1.5", "gwei").unwrap();
+        assert_eq!(e.gas_price(), Some(U256::from(1_500_000_000u64)));
+        assert!(e.with_gas_price("0.1", "wei").is_err());
+    }
+
+    #[tokio::test]
+    async fn call_with_options() {
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap();
+        let (contract, from, hash) = (Address::random(), Address::random(), H256::random());
+        e.call_transaction(contract, Bytes(vec![1])).await.unwrap();
+        for block in [BlockTag::Finalized, BlockTag::Number(100), BlockTag::Hash(hash)] {
+            let options = CallOptions::default().from(from).block(block);
+            assert_eq!(e.call_transaction_with_options(contract, Bytes(vec![1]), options).await.unwrap(), Bytes(vec![1]));
+        }
+        let params = node.params("eth_call");
+        assert_eq!(params[0][0]["from"], serde_json::Value::Null);
+        assert_eq!(params[0][1], "latest");
+        assert_eq!(params[1][0]["from"], format!("{:?}", from));
+        assert_eq!(params[1][1], "finalized");
+        assert_eq!(params[2][1], "0x64");
+        assert_eq!(params[3][1]["blockHash"], format!("{:?}", hash));
+    }
+
+    #[test]
+    fn revert_reason() {
+        let data = hex::decode("08c379a0").unwrap().into_iter()
+            .chain(web3::ethabi::encode(&[web3::ethabi::Token::String("nope".to_string())]))
+            .collect::<Vec<u8>>();
+        assert_eq!(decode_revert(&data).unwrap(), "nope");
+        let panic = hex::decode(format!("4e487b71{:064x}", 0x11)).unwrap();
+        assert_eq!(decode_revert(&panic).unwrap(), "panic 0x11");
+        assert_eq!(decode_revert(&[0xde, 0xad, 0xbe, 0xef]).unwrap(), "custom error 0xdeadbeef");
+        assert_eq!(decode_revert(&[]), None);
+    }
+
+    #[tokio::test]
+    async fn simulation_mode() {
+        let node = mock_node().await;
+        let e = Engine::new(node.url).unwrap().with_simulation(true);
+        let (from, _) = e.parse_private_key_to_address(PRIVATE_KEY).unwrap();
+        let simulation = e.simulate(from, Some(Address::random()), None, Some(Bytes(vec![1]))).await.unwrap();
+        assert!(simulation.success);
+        assert_eq!(simulation.output, Bytes(vec![1]));
+        assert_eq!(simulation.gas_estimate, Some(U256::from(21000)));
+
+        // 只签名不广播, 等待确认返回成功的回执
+        let (hash, tx) = e.send_transaction_by_data(Address::random(), Bytes(vec![1]), PRIVATE_KEY).await.unwrap();
+        assert_eq!(Some(hash), tx.hash);
+        let receipt = e.wait_transaction_success(hash).await.unwrap();
+        assert_eq!(receipt.transaction_hash, hash);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+        assert_eq!(node.calls("eth_getTransactionReceipt"), 0);
+
+        // 部署合约返回按 nonce 计算的地址
+        let (address, _) = e.deploy(&crate::abi::disperse::DisperseAbi::new(), Bytes(vec![0x60, 0x00]), &[], PRIVATE_KEY).await.unwrap();
+        assert_eq!(address, create_address(from, U256::from(7)));
+
+        let mut responses = default_responses();
+        let reason = "0x08c379a0".to_string() + &hex::encode(web3::ethabi::encode(&[web3::ethabi::Token::String("nope".to_string())]));
+        responses.insert("eth_call", rpc_error(3, "execution reverted", Some(&reason)));
+        let node = MockNode::start(responses).await;
+        let e = Engine::new(node.url).unwrap().with_simulation(true);
+        let simulation = e.simulate(from, Some(Address::random()), None, None).await.unwrap();
+        assert!(!simulation.success);
+        assert_eq!(simulation.revert_reason.as_deref(), Some("nope"));
+        let result = e.send_transaction_by_value(Address::random(), U256::one(), PRIVATE_KEY).await;
+        assert!(matches!(result, Err(Error::ErrReverted(reason)) if reason == "nope"));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+    }
+}
@@ -3,6 +3,18 @@ pub mod utils;
 pub mod abi;
 pub mod contract;
 pub mod tx;
+pub mod codegen;
+pub mod watcher;
+pub mod amount;
+pub mod payout;
+pub mod merkle;
+pub mod sweep;
+
+#[cfg(test)]
+mod mock;
+
+pub use web3;
+pub use async_trait;
 
 #[cfg(test)]
 mod tests {
@@ -10,7 +22,7 @@ mod tests {
 
     #[tokio::test]
     async fn new_engine() {
-        let e = Engine::new("http://127.0.0.1:8545").unwrap();
+        let _e = Engine::new("http://127.0.0.1:8545").unwrap();
     }
 
     #[tokio::test]
@@ -0,0 +1,299 @@
+// 领取式空投的 Merkle 树. 叶子为 (index, account, amount), 节点按 OpenZeppelin MerkleProof 的方式
+// 对两个子节点排序后哈希, 树的布局与 @openzeppelin/merkle-tree 的 StandardMerkleTree 相同.
+//
+// 叶子编码:
+// Packed   keccak256(abi.encodePacked(index, account, amount)), Uniswap MerkleDistributor 的写法
+// Standard keccak256(keccak256(abi.encode(index, account, amount))), StandardMerkleTree 的写法
+//
+// Uniswap 的脚本逐层两两合并, 叶子数量为2的幂时与这里的根相同, 其他数量时根可能不同,
+// 但生成的证明都能通过 MerkleDistributor 合约的验证.
+
+use std::collections::HashSet;
+use std::path::Path;
+use serde_json::{json, Map, Value};
+use thiserror::Error;
+use web3::ethabi::{encode, Token};
+use web3::signing::keccak256;
+use web3::types::{Address, H256, U256};
+use crate::utils::{AddressUtils, ToHex};
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("merkle tree has no leaves")]
+    ErrEmpty,
+    #[error("duplicate index {0}")]
+    ErrDuplicateIndex(u64),
+    #[error("duplicate account {0:?}")]
+    ErrDuplicateAccount(Address),
+    #[error("io error")]
+    ErrIo(#[from] std::io::Error),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LeafEncoding {
+    #[default]
+    Packed,
+    Standard,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MerkleLeaf {
+    pub index: u64,
+    pub account: Address,
+    pub amount: U256,
+}
+
+impl MerkleLeaf {
+    pub fn hash(&self, encoding: LeafEncoding) -> H256 {
+        match encoding {
+            LeafEncoding::Packed => {
+                let mut data = [0u8; 84];
+                U256::from(self.index).to_big_endian(&mut data[..32]);
+                data[32..52].copy_from_slice(self.account.as_bytes());
+                self.amount.to_big_endian(&mut data[52..]);
+                H256::from(keccak256(&data))
+            }
+            LeafEncoding::Standard => {
+                let data = encode(&[Token::Uint(U256::from(self.index)), Token::Address(self.account), Token::Uint(self.amount)]);
+                H256::from(keccak256(&keccak256(&data)))
+            }
+        }
+    }
+}
+
+// OpenZeppelin MerkleProof 的 _hashPair: 较小的在前
+pub fn hash_pair(a: H256, b: H256) -> H256 {
+    let (first, second) = if a < b { (a, b) } else { (b, a) };
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(first.as_bytes());
+    data[32..].copy_from_slice(second.as_bytes());
+    H256::from(keccak256(&data))
+}
+
+pub fn verify(proof: &[H256], root: H256, leaf: H256) -> bool {
+    proof.iter().fold(leaf, |hash, sibling| hash_pair(hash, *sibling)) == root
+}
+
+#[derive(Debug, Clone)]
+pub struct MerkleTree {
+    encoding: LeafEncoding,
+    leaves: Vec<MerkleLeaf>,
+    // 完全二叉树, tree[0] 为根, tree[i] 的子节点为 tree[2i+1] 和 tree[2i+2]
+    tree: Vec<H256>,
+    // leaves[i] 在 tree 中的位置
+    positions: Vec<usize>,
+}
+
+impl MerkleTree {
+    pub fn new(leaves: Vec<MerkleLeaf>, encoding: LeafEncoding) -> Result<Self> {
+        if leaves.is_empty() {
+            return Err(Error::ErrEmpty);
+        }
+        let mut indexes = HashSet::new();
+        let mut accounts = HashSet::new();
+        for leaf in &leaves {
+            if !indexes.insert(leaf.index) {
+                return Err(Error::ErrDuplicateIndex(leaf.index));
+            }
+            if !accounts.insert(leaf.account) {
+                return Err(Error::ErrDuplicateAccount(leaf.account));
+            }
+        }
+
+        // 叶子按哈希排序后倒序放在数组末尾
+        let hashes: Vec<H256> = leaves.iter().map(|leaf| leaf.hash(encoding)).collect();
+        let mut order: Vec<usize> = (0..leaves.len()).collect();
+        order.sort_by_key(|&i| hashes[i]);
+        let len = 2 * leaves.len() - 1;
+        let mut tree = vec![H256::zero(); len];
+        let mut positions = vec![0; leaves.len()];
+        for (i, &leaf) in order.iter().enumerate() {
+            positions[leaf] = len - 1 - i;
+            tree[len - 1 - i] = hashes[leaf];
+        }
+        for i in (0..len - leaves.len()).rev() {
+            tree[i] = hash_pair(tree[2 * i + 1], tree[2 * i + 2]);
+        }
+        Ok(Self { encoding, leaves, tree, positions })
+    }
+
+    // 按传入顺序分配 index
+    pub fn from_balances(balances: &[(Address, U256)], encoding: LeafEncoding) -> Result<Self> {
+        let leaves = balances.iter().enumerate()
+            .map(|(i, (account, amount))| MerkleLeaf { index: i as u64, account: *account, amount: *amount })
+            .collect();
+        Self::new(leaves, encoding)
+    }
+
+    pub fn root(&self) -> H256 {
+        self.tree[0]
+    }
+
+    pub fn encoding(&self) -> LeafEncoding {
+        self.encoding
+    }
+
+    pub fn leaves(&self) -> &[MerkleLeaf] {
+        &self.leaves
+    }
+
+    pub fn total(&self) -> U256 {
+        self.leaves.iter().fold(U256::zero(), |sum, leaf| sum.saturating_add(leaf.amount))
+    }
+
+    pub fn leaf(&self, account: Address) -> Option<&MerkleLeaf> {
+        self.leaves.iter().find(|leaf| leaf.account == account)
+    }
+
+    // leaves[i] 的证明, 从叶子到根
+    pub fn proof(&self, i: usize) -> Option<Vec<H256>> {
+        let mut position = *self.positions.get(i)?;
+        let mut proof = Vec::new();
+        while position > 0 {
+            let sibling = if position % 2 == 1 { position + 1 } else { position - 1 };
+            proof.push(self.tree[sibling]);
+            position = (position - 1) / 2;
+        }
+        Some(proof)
+    }
+
+    pub fn proof_of(&self, account: Address) -> Option<(MerkleLeaf, Vec<H256>)> {
+        let i = self.leaves.iter().position(|leaf| leaf.account == account)?;
+        Some((self.leaves[i], self.proof(i)?))
+    }
+
+    pub fn verify(&self, leaf: &MerkleLeaf, proof: &[H256]) -> bool {
+        verify(proof, self.root(), leaf.hash(self.encoding))
+    }
+
+    // 与 Uniswap merkle-distributor 脚本相同的格式:
+    // {"merkleRoot": "0x..", "tokenTotal": "0x..", "claims": {"0xChecksum": {"index": 0, "amount": "0x..", "proof": ["0x.."]}}}
+    pub fn to_json(&self) -> Value {
+        let mut claims = Map::new();
+        for (i, leaf) in self.leaves.iter().enumerate() {
+            let proof: Vec<String> = self.proof(i).unwrap_or_default().iter().map(|h| h.to_hex()).collect();
+            claims.insert(leaf.account.checksum_address(), json!({
+                "index": leaf.index,
+                "amount": format!("{:#x}", leaf.amount),
+                "proof": proof,
+            }));
+        }
+        json!({
+            "merkleRoot": self.root().to_hex(),
+            "tokenTotal": format!("{:#x}", self.total()),
+            "claims": claims,
+        })
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result {
+        std::fs::write(path, serde_json::to_string_pretty(&self.to_json()).unwrap())?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use web3::ethabi::{encode, Token};
+    use web3::signing::keccak256;
+    use web3::types::{Address, H256, U256};
+    use crate::merkle::{hash_pair, verify, Error, LeafEncoding, MerkleLeaf, MerkleTree};
+    use crate::utils::AddressUtils;
+
+    #[test]
+    fn merkle_tree_proofs() {
+        let balances: Vec<_> = (1..=5).map(|i| (Address::random(), U256::from(i * 100))).collect();
+        for encoding in [LeafEncoding::Packed, LeafEncoding::Standard] {
+            let tree = MerkleTree::from_balances(&balances, encoding).unwrap();
+            for (i, leaf) in tree.leaves().iter().enumerate() {
+                let proof = tree.proof(i).unwrap();
+                assert!(tree.verify(leaf, &proof));
+                // 修改金额后证明无效
+                let forged = MerkleLeaf { amount: leaf.amount + 1, ..*leaf };
+                assert!(!tree.verify(&forged, &proof));
+            }
+            assert_eq!(tree.total(), U256::from(1500));
+        }
+    }
+
+    #[test]
+    fn merkle_tree_layout() {
+        let balances: Vec<_> = (1..=3).map(|i| (Address::random(), U256::from(i))).collect();
+        let tree = MerkleTree::from_balances(&balances, LeafEncoding::Standard).unwrap();
+        let mut hashes: Vec<H256> = tree.leaves().iter().map(|leaf| leaf.hash(LeafEncoding::Standard)).collect();
+        hashes.sort();
+        // StandardMerkleTree: [root, H(h1, h0), h2, h1, h0]
+        assert_eq!(tree.root(), hash_pair(hash_pair(hashes[0], hashes[1]), hashes[2]));
+
+        let single = MerkleTree::from_balances(&balances[..1], LeafEncoding::Packed).unwrap();
+        assert!(single.proof(0).unwrap().is_empty());
+        assert!(verify(&[], single.root(), single.leaves()[0].hash(LeafEncoding::Packed)));
+    }
+
+    fn h256(hex: &str) -> H256 {
+        H256::from_str(hex).unwrap()
+    }
+
+    fn vector_balances(count: usize, amount: impl Fn(usize) -> U256) -> Vec<(Address, U256)> {
+        ["11", "22", "33", "44", "55"][..count].iter().enumerate()
+            .map(|(i, byte)| (Address::from_str(&byte.repeat(20)).unwrap(), amount(i)))
+            .collect()
+    }
+
+    #[test]
+    fn merkle_tree_openzeppelin_vectors() {
+        // @openzeppelin/merkle-tree README: StandardMerkleTree.of([[0x1111.., "5000000000000000000"], [0x2222.., "2500000000000000000"]], ["address", "uint256"])
+        let leaf = |account: &str, amount: u64| {
+            let data = encode(&[Token::Address(Address::from_str(account).unwrap()), Token::Uint(U256::from(amount))]);
+            H256::from(keccak256(&keccak256(&data)))
+        };
+        let first = leaf("1111111111111111111111111111111111111111", 5_000_000_000_000_000_000);
+        let second = leaf("2222222222222222222222222222222222222222", 2_500_000_000_000_000_000);
+        assert_eq!(second, h256("b92c48e9d7abe27fd8dfd6b5dfdbfb1c9a463f80c712b66f3a5180a090cccafc"));
+        let root = h256("d4dee0beab2d53f2cc83e567171bd2820e49898130a22622b10ead383e90bd77");
+        assert_eq!(hash_pair(first, second), root);
+        assert!(verify(&[second], root, first));
+
+        // StandardMerkleTree.of([[0, 0x1111.., 1e18], ..., [4, 0x5555.., 5e18]], ["uint256", "address", "uint256"])
+        let balances = vector_balances(5, |i| U256::exp10(18) * (i + 1));
+        let tree = MerkleTree::from_balances(&balances, LeafEncoding::Standard).unwrap();
+        assert_eq!(tree.root(), h256("f0eb7e8357552b8722f7829b81eefd8239d2378f6dd63588ec3876d39798d7e5"));
+        assert_eq!(tree.proof(2).unwrap(), vec![
+            h256("2c9eb4e338e36a10b01400b6e6de581a5812c751653003a10644cacd6e64b713"),
+            h256("eb728624ae06a51d14c3864a1c4dbf39cfa562f9784e4091328aa6ce23ae25bd"),
+            h256("59186333fc02a5c901020865c76a4b6a5894f475f9730cb21e857a5070b6a4a8"),
+        ]);
+    }
+
+    #[test]
+    fn merkle_tree_uniswap_vector() {
+        // Uniswap merkle-distributor BalanceTree([{account: 0x1111.., amount: 100}, ..., {account: 0x4444.., amount: 103}])
+        let balances = vector_balances(4, |i| U256::from(100 + i));
+        let tree = MerkleTree::from_balances(&balances, LeafEncoding::Packed).unwrap();
+        assert_eq!(tree.leaves()[0].hash(LeafEncoding::Packed), h256("81d0c9e91aa5221ffb35dc43b148c071fd8544baf431e949e57286f2bf8af3f8"));
+        assert_eq!(tree.root(), h256("80f3c0a1f06e007b3ddc785196dae2a2a65bd492b487d3deb9fbe34bfa7c531d"));
+        assert_eq!(tree.proof(0).unwrap(), vec![
+            h256("c33210be32136cedee9ea3069bb95203cf60eab1d3b6a18f4fa07bf3d3816d8f"),
+            h256("aed21ef430cc54e217a86499c809e79ffc155be920fd7d2d7c1addd3244a7d5a"),
+        ]);
+    }
+
+    #[test]
+    fn merkle_tree_json() {
+        let account = Address::random();
+        let other = Address::random();
+        let tree = MerkleTree::from_balances(&[(account, U256::from(255)), (other, U256::one())], LeafEncoding::Packed).unwrap();
+        let json = tree.to_json();
+        assert_eq!(json["tokenTotal"], "0x100");
+        let claim = &json["claims"][account.checksum_address()];
+        assert_eq!(claim["index"], 0);
+        assert_eq!(claim["amount"], "0xff");
+        assert_eq!(claim["proof"][0], format!("{:?}", tree.leaves()[1].hash(LeafEncoding::Packed)));
+
+        let leaf = MerkleLeaf { index: 0, account, amount: U256::one() };
+        assert!(matches!(MerkleTree::new(vec![leaf, leaf], LeafEncoding::Packed), Err(Error::ErrDuplicateIndex(0))));
+        assert!(matches!(MerkleTree::new(vec![], LeafEncoding::Packed), Err(Error::ErrEmpty)));
+    }
+}
@@ -0,0 +1,153 @@
+// 测试用的JSON-RPC节点, 按方法名返回固定结果, 并记录收到的请求
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::TcpListener;
+use web3::types::{Address, Bytes, Log, H256};
+
+// 按请求动态生成响应, 返回 None 时使用固定响应
+pub type Handler = Arc<dyn Fn(&str, &Value) -> Option<Value> + Send + Sync>;
+
+#[derive(Clone)]
+pub struct MockNode {
+    pub url: &'static str,
+    requests: Arc<Mutex<Vec<Value>>>,
+}
+
+// 发送交易所需的最小响应集合, 测试中按需覆盖
+pub fn default_responses() -> HashMap<&'static str, Value> {
+    let receipt = web3::types::TransactionReceipt { status: Some(1.into()), ..Default::default() };
+    let mut responses = HashMap::new();
+    responses.insert("eth_chainId", json!("0x539"));
+    responses.insert("eth_estimateGas", json!("0x5208"));
+    responses.insert("eth_getTransactionCount", json!("0x7"));
+    responses.insert("eth_sendRawTransaction", json!(format!("{:?}", web3::types::H256::repeat_byte(1))));
+    responses.insert("eth_getTransactionReceipt", serde_json::to_value(receipt).unwrap());
+    responses.insert("eth_call", json!("0x01"));
+    responses
+}
+
+// 作为响应时节点返回JSON-RPC错误, 如合约revert
+pub fn rpc_error(code: i64, message: &str, data: Option<&str>) -> Value {
+    json!({"error": {"code": code, "message": message, "data": data}})
+}
+
+// 解析事件用的日志, 只有 topics 和 data
+pub fn make_log(topics: Vec<H256>, data: Vec<u8>) -> Log {
+    Log {
+        address: Address::random(),
+        topics,
+        data: Bytes(data),
+        block_hash: None,
+        block_number: None,
+        transaction_hash: None,
+        transaction_index: None,
+        log_index: None,
+        transaction_log_index: None,
+        log_type: None,
+        removed: None,
+    }
+}
+
+impl MockNode {
+    pub async fn start(responses: HashMap<&'static str, Value>) -> Self {
+        Self::start_with(responses, |_, _| None).await
+    }
+
+    // handler 的参数为方法名和 params
+    pub async fn start_with<F>(responses: HashMap<&'static str, Value>, handler: F) -> Self
+        where F: Fn(&str, &Value) -> Option<Value> + Send + Sync + 'static {
+        let handler: Handler = Arc::new(handler);
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url: &'static str = Box::leak(format!("http://{}", listener.local_addr().unwrap()).into_boxed_str());
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let responses = Arc::new(responses);
+
+        let node_requests = requests.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = match listener.accept().await {
+                    Ok(conn) => conn,
+                    Err(_) => return,
+                };
+                let requests = node_requests.clone();
+                let responses = responses.clone();
+                let handler = handler.clone();
+                tokio::spawn(async move {
+                    let (read, mut write) = stream.into_split();
+                    let mut reader = BufReader::new(read);
+                    loop {
+                        let mut content_length = 0;
+                        loop {
+                            let mut line = String::new();
+                            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                                return;
+                            }
+                            let line = line.trim_end();
+                            if line.is_empty() {
+                                break;
+                            }
+                            if let Some((name, value)) = line.split_once(':') {
+                                if name.eq_ignore_ascii_case("content-length") {
+                                    content_length = value.trim().parse().unwrap_or(0);
+                                }
+                            }
+                        }
+                        let mut body = vec![0u8; content_length];
+                        if reader.read_exact(&mut body).await.is_err() {
+                            return;
+                        }
+                        let request: Value = serde_json::from_slice(&body).unwrap();
+                        requests.lock().unwrap().push(request.clone());
+
+                        let response = match request {
+                            Value::Array(calls) => Value::Array(calls.iter().map(|c| respond(&responses, &handler, c)).collect()),
+                            call => respond(&responses, &handler, &call),
+                        };
+                        let response = response.to_string();
+                        let head = format!("HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n", response.len());
+                        if write.write_all(head.as_bytes()).await.is_err() || write.write_all(response.as_bytes()).await.is_err() {
+                            return;
+                        }
+                    }
+                });
+            }
+        });
+
+        Self { url, requests }
+    }
+
+    // 收到的HTTP请求数量, 一个batch算一次
+    pub fn http_requests(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    // 某个方法被调用的次数, batch中的调用分别计数
+    pub fn calls(&self, method: &str) -> usize {
+        self.params(method).len()
+    }
+
+    // 某个方法每次调用的参数
+    pub fn params(&self, method: &str) -> Vec<Value> {
+        self.requests.lock().unwrap().iter()
+            .flat_map(|r| match r {
+                Value::Array(calls) => calls.clone(),
+                call => vec![call.clone()],
+            })
+            .filter(|c| c["method"] == method)
+            .map(|c| c["params"].clone())
+            .collect()
+    }
+}
+
+fn respond(responses: &HashMap<&'static str, Value>, handler: &Handler, call: &Value) -> Value {
+    let id = call["id"].clone();
+    let method = call["method"].as_str().unwrap_or_default();
+    let dynamic = handler(method, &call["params"]);
+    match dynamic.as_ref().or_else(|| responses.get(method)) {
+        Some(result) if result.get("error").is_some() => json!({"jsonrpc": "2.0", "id": id, "error": result["error"]}),
+        Some(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        None => json!({"jsonrpc": "2.0", "id": id, "error": {"code": -32601, "message": "method not found"}}),
+    }
+}
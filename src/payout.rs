@@ -0,0 +1,652 @@
+// 批量转账(发工资/空投): 从CSV读取 address,amount, 校验后按顺序分配nonce签名, 并发广播并等待确认.
+//
+// 每笔交易签名后先把 nonce 和交易hash 写入结果CSV再广播, 程序中断后用同一个结果文件重新运行,
+// 已成功/失败的行会跳过, 已签名的行会先检查链上状态, 不会重复付款.
+// 结果文件中不在本次CSV里的行原样保留, 之后用原来的CSV重新运行时同样会跳过.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use thiserror::Error;
+use web3::futures::{stream, StreamExt};
+use web3::types::{Address, BlockNumber, Bytes, H256, U256, U64};
+use crate::abi::erc20::Erc20ContractAbi;
+use crate::amount::Amount;
+use crate::contract::ContractLiving;
+use crate::contract::erc20::{Erc20, Erc20Contract};
+use crate::engine::Engine;
+use crate::utils::AddressUtils;
+use crate::utils::units::parse_units;
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("engine error")]
+    ErrEngine(#[from] crate::engine::Error),
+    #[error("contract error")]
+    ErrContract(#[from] crate::contract::Error),
+    #[error("abi error")]
+    ErrAbi(#[from] crate::abi::Error),
+    #[error("amount error")]
+    ErrAmount(#[from] crate::amount::Error),
+    #[error("io error")]
+    ErrIo(#[from] std::io::Error),
+    #[error("line {0}: {1}")]
+    ErrCsv(usize, String),
+    #[error("line {0}: invalid checksum address {1}")]
+    ErrChecksum(usize, String),
+    #[error("line {1}: duplicate address {0:?}, first seen at line {2}")]
+    ErrDuplicate(Address, usize, usize),
+    #[error("insufficient balance: required {required}, available {available}")]
+    ErrInsufficientBalance { required: U256, available: U256 },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutRow {
+    // CSV中的行号, 用于报错
+    pub line: usize,
+    pub to: Address,
+    pub amount: U256,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayoutStatus {
+    Pending,
+    // 已签名并记录hash, 可能已经广播
+    Signed,
+    Success,
+    // 交易已上链但执行失败
+    Failed,
+    // 签名或广播失败, 下次运行会重试
+    Error,
+    // 记录的交易找不到且nonce已被其他交易使用, 需要人工确认是否已付款, 之后运行不会处理
+    Review,
+}
+
+impl PayoutStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PayoutStatus::Pending => "pending",
+            PayoutStatus::Signed => "signed",
+            PayoutStatus::Success => "success",
+            PayoutStatus::Failed => "failed",
+            PayoutStatus::Error => "error",
+            PayoutStatus::Review => "review",
+        }
+    }
+
+    fn parse(s: &str) -> Option<Self> {
+        Some(match s {
+            "pending" => PayoutStatus::Pending,
+            "signed" => PayoutStatus::Signed,
+            "success" => PayoutStatus::Success,
+            "failed" => PayoutStatus::Failed,
+            "error" => PayoutStatus::Error,
+            "review" => PayoutStatus::Review,
+            _ => return None,
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayoutResult {
+    pub to: Address,
+    pub amount: U256,
+    pub nonce: Option<U256>,
+    pub hash: Option<H256>,
+    pub status: PayoutStatus,
+    pub message: String,
+}
+
+impl PayoutResult {
+    fn new(row: &PayoutRow) -> Self {
+        Self { to: row.to, amount: row.amount, nonce: None, hash: None, status: PayoutStatus::Pending, message: String::new() }
+    }
+}
+
+enum Resolved {
+    // 已有结果, 或不是已签名的行
+    Done,
+    // 在交易池中等待确认
+    Waiting,
+    // 节点上找不到, 需要用记录的nonce重新发送
+    Dropped(U256),
+}
+
+const RESULT_HEADER: &str = "address,amount,nonce,hash,status,message";
+
+// message 中记录的被重新发送替换掉的交易hash: "replaced <hash> <hash>; ..."
+fn replaced_hashes(message: &str) -> Option<&str> {
+    message.strip_prefix("replaced ").and_then(|m| m.split(';').next())
+}
+
+// 解析 address,amount 格式的CSV, amount 为按 decimals 换算前的数量(如 "1.5"), 第一行可以是表头
+pub fn parse_csv(content: &str, decimals: u8) -> Result<Vec<PayoutRow>> {
+    let mut rows = Vec::new();
+    let mut seen: HashMap<Address, usize> = HashMap::new();
+    for (i, line) in content.lines().enumerate() {
+        let line_no = i + 1;
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.split(',').map(|f| f.trim()).collect();
+        if fields.len() != 2 {
+            return Err(Error::ErrCsv(line_no, format!("expected 2 fields, got {}", fields.len())));
+        }
+        if rows.is_empty() && seen.is_empty() && fields[0].eq_ignore_ascii_case("address") {
+            continue;
+        }
+
+        let to = parse_address(line_no, fields[0])?;
+        let amount = parse_units(fields[1], decimals).map_err(|e| Error::ErrCsv(line_no, e.to_string()))?;
+        if amount.is_zero() {
+            return Err(Error::ErrCsv(line_no, "zero amount".to_string()));
+        }
+        if let Some(first) = seen.insert(to, line_no) {
+            return Err(Error::ErrDuplicate(to, line_no, first));
+        }
+        rows.push(PayoutRow { line: line_no, to, amount });
+    }
+    Ok(rows)
+}
+
+// 全小写/全大写的地址不校验, 大小写混合时必须是正确的 checksum 地址
+fn parse_address(line: usize, value: &str) -> Result<Address> {
+    let hex = value.strip_prefix("0x").unwrap_or(value);
+    if hex.len() != 40 {
+        return Err(Error::ErrCsv(line, format!("invalid address {}", value)));
+    }
+    let address = Address::from_str(hex).map_err(|_| Error::ErrCsv(line, format!("invalid address {}", value)))?;
+    let mixed = hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if mixed && address.checksum_address()[2..] != *hex {
+        return Err(Error::ErrChecksum(line, value.to_string()));
+    }
+    Ok(address)
+}
+
+fn load_results(path: &Path) -> Result<Vec<PayoutResult>> {
+    let content = match std::fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e.into()),
+    };
+    let mut results = Vec::new();
+    for (i, line) in content.lines().enumerate().skip(1) {
+        let err = |msg: &str| Error::ErrCsv(i + 1, format!("result file: {}", msg));
+        let fields: Vec<&str> = line.splitn(6, ',').collect();
+        if fields.len() != 6 {
+            return Err(err("expected 6 fields"));
+        }
+        let optional = |value: &str| if value.is_empty() { None } else { Some(value.to_string()) };
+        results.push(PayoutResult {
+            to: Address::from_str(fields[0].trim_start_matches("0x")).map_err(|_| err("invalid address"))?,
+            amount: U256::from_dec_str(fields[1]).map_err(|_| err("invalid amount"))?,
+            nonce: optional(fields[2]).map(|n| U256::from_dec_str(&n)).transpose().map_err(|_| err("invalid nonce"))?,
+            hash: optional(fields[3]).map(|h| H256::from_str(h.trim_start_matches("0x"))).transpose().map_err(|_| err("invalid hash"))?,
+            status: PayoutStatus::parse(fields[4]).ok_or_else(|| err("invalid status"))?,
+            message: fields[5].to_string(),
+        });
+    }
+    Ok(results)
+}
+
+// 先写临时文件再重命名, 避免写入一半时崩溃导致结果文件损坏
+fn save_results(path: &Path, results: &[PayoutResult]) -> Result {
+    let mut content = String::from(RESULT_HEADER);
+    content.push('\n');
+    for r in results {
+        content.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            r.to.checksum_address(),
+            r.amount,
+            r.nonce.map(|n| n.to_string()).unwrap_or_default(),
+            r.hash.map(|h| format!("{:?}", h)).unwrap_or_default(),
+            r.status.as_str(),
+            r.message.replace(['\n', '\r'], " "),
+        ));
+    }
+    let tmp = path.with_extension("tmp");
+    std::fs::write(&tmp, content)?;
+    std::fs::rename(tmp, path)?;
+    Ok(())
+}
+
+pub struct Payout {
+    engine: Engine,
+    // None 为原生币
+    token: Option<Erc20>,
+    concurrency: usize,
+    result_path: PathBuf,
+}
+
+impl Payout {
+    pub fn native(engine: Engine, result_path: impl Into<PathBuf>) -> Self {
+        Self { engine, token: None, concurrency: 8, result_path: result_path.into() }
+    }
+
+    pub fn token(token: Erc20, result_path: impl Into<PathBuf>) -> Self {
+        Self { engine: token.engine().clone(), token: Some(token), concurrency: 8, result_path: result_path.into() }
+    }
+
+    // 同时等待确认的交易数量
+    pub fn concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    pub async fn decimals(&self) -> Result<u8> {
+        match &self.token {
+            Some(token) => Ok(Amount::from_decimals(U256::zero(), token.decimals().await?)?.decimals),
+            None => Ok(18),
+        }
+    }
+
+    pub async fn load_csv(&self, path: impl AsRef<Path>) -> Result<Vec<PayoutRow>> {
+        let content = std::fs::read_to_string(path)?;
+        parse_csv(&content, self.decimals().await?)
+    }
+
+    async fn balance(&self, account: Address) -> Result<U256> {
+        match &self.token {
+            Some(token) => Ok(token.balance_of(account).await?),
+            None => Ok(self.engine.web3().eth().balance(account, None).await.map_err(crate::engine::Error::from)?),
+        }
+    }
+
+    async fn sign(&self, row: &PayoutResult, nonce: U256, private_key: &str) -> Result<(Bytes, H256)> {
+        let (raw, tx) = match &self.token {
+            Some(token) => {
+                let data = token.abi().transfer(row.to, row.amount)?;
+                self.engine.sign_transaction(Some(*token.contract()), None, Some(data), Some(nonce), private_key).await?
+            }
+            None => self.engine.sign_transaction(Some(row.to), Some(row.amount), None, Some(nonce), private_key).await?,
+        };
+        Ok((raw, tx.hash.unwrap_or_default()))
+    }
+
+    // 已签名但未确认的交易: 已上链的更新状态, 在交易池中的继续等待, 都找不到的用原来的nonce重新签名发送.
+    // nonce 已被其他交易使用时(在别的节点上链或被替换)不能重发, 标记为需要人工确认
+    async fn resolve_signed(&self, from: Address, result: &mut PayoutResult) -> Result<Resolved> {
+        let hash = match (result.status, result.hash) {
+            (PayoutStatus::Signed, Some(hash)) => hash,
+            _ => return Ok(Resolved::Done),
+        };
+        let eth = self.engine.web3().eth();
+        if let Some(receipt) = eth.transaction_receipt(hash).await.map_err(crate::engine::Error::from)? {
+            result.status = if receipt.status == Some(U64::zero()) { PayoutStatus::Failed } else { PayoutStatus::Success };
+            return Ok(Resolved::Done);
+        }
+        if eth.transaction(hash.into()).await.map_err(crate::engine::Error::from)?.is_some() {
+            return Ok(Resolved::Waiting);
+        }
+        match result.nonce {
+            Some(nonce) => {
+                let used = eth.transaction_count(from, Some(BlockNumber::Latest)).await.map_err(crate::engine::Error::from)?;
+                if nonce < used {
+                    result.status = PayoutStatus::Review;
+                    result.message = format!("nonce {} already used by another transaction, check {:?} manually", nonce, hash);
+                    return Ok(Resolved::Done);
+                }
+                Ok(Resolved::Dropped(nonce))
+            }
+            None => {
+                result.status = PayoutStatus::Pending;
+                Ok(Resolved::Done)
+            }
+        }
+    }
+
+    // 原生币转账需要的手续费, 估算失败的行在签名时会记录错误, 这里不计入
+    async fn fees(&self, from: Address, rows: &[&PayoutResult]) -> Result<U256> {
+        if self.token.is_some() || rows.is_empty() {
+            return Ok(U256::zero());
+        }
+        let gas_price = match self.engine.gas_price() {
+            Some(gas_price) => gas_price,
+            None => self.engine.web3().eth().gas_price().await.map_err(crate::engine::Error::from)?,
+        };
+        let mut fees = U256::zero();
+        for row in rows {
+            if let Ok(gas) = self.engine.estimate_gas(from, Some(row.to), Some(row.amount), None).await {
+                fees = fees.saturating_add(gas.saturating_mul(gas_price));
+            }
+        }
+        Ok(fees)
+    }
+
+    // 模拟模式下交易没有广播, 不写结果文件, 避免之后真正运行时把这些行当作已付款跳过
+    fn save(&self, results: &[PayoutResult]) -> Result {
+        if self.engine.is_simulation() {
+            return Ok(());
+        }
+        save_results(&self.result_path, results)
+    }
+
+    pub async fn run(&self, rows: &[PayoutRow], private_key: &str) -> Result<Vec<PayoutResult>> {
+        let (from, _) = self.engine.parse_private_key_to_address(private_key)?;
+
+        let previous = load_results(&self.result_path)?;
+        let order: Vec<Address> = previous.iter().map(|r| r.to).collect();
+        let mut previous: HashMap<Address, PayoutResult> = previous.into_iter().map(|r| (r.to, r)).collect();
+        let mut results = Vec::with_capacity(rows.len());
+        for row in rows {
+            match previous.remove(&row.to) {
+                Some(r) if r.amount != row.amount => {
+                    return Err(Error::ErrCsv(row.line, format!("amount differs from result file: {}", r.amount)));
+                }
+                Some(r) => results.push(r),
+                None => results.push(PayoutResult::new(row)),
+            }
+        }
+        // 不在本次CSV中的行放在最后原样写回, 不做处理, 也不会再次付款
+        let count = results.len();
+        results.extend(order.iter().filter_map(|to| previous.remove(to)));
+
+        let mut waiting = Vec::new();
+        let mut dropped = Vec::new();
+        for (i, result) in results.iter_mut().enumerate().take(count) {
+            match self.resolve_signed(from, result).await? {
+                Resolved::Done => {}
+                Resolved::Waiting => waiting.push((i, None)),
+                Resolved::Dropped(nonce) => dropped.push((i, nonce)),
+            }
+        }
+        self.save(&results)?;
+
+        let remaining: Vec<usize> = (0..count)
+            .filter(|i| matches!(results[*i].status, PayoutStatus::Pending | PayoutStatus::Error))
+            .collect();
+        let unpaid: Vec<&PayoutResult> = dropped.iter().map(|(i, _)| *i).chain(remaining.iter().copied()).map(|i| &results[i]).collect();
+        let required = unpaid.iter()
+            .try_fold(U256::zero(), |sum, r| sum.checked_add(r.amount))
+            .ok_or(crate::amount::Error::ErrOverflow)?;
+        let required = required.checked_add(self.fees(from, &unpaid).await?).ok_or(crate::amount::Error::ErrOverflow)?;
+        let available = self.balance(from).await?;
+        if required > available {
+            return Err(Error::ErrInsufficientBalance { required, available });
+        }
+
+        // 被丢弃的交易用原来的nonce重新签名, 交易内容不变, 不会因为nonce变化而重复付款.
+        // 被替换的hash记录在 message 中, 作为原交易的凭证
+        for (i, nonce) in dropped {
+            let replaced = replaced_hashes(&results[i].message).map(str::to_string);
+            match self.sign(&results[i], nonce, private_key).await {
+                Ok((raw, hash)) => {
+                    let result = &mut results[i];
+                    let original = format!("{:?}", result.hash.replace(hash).unwrap_or_default());
+                    result.message = format!("replaced {}", replaced.map(|h| format!("{} {}", h, original)).unwrap_or(original));
+                    waiting.push((i, Some(raw)));
+                }
+                Err(e) => results[i].message = match replaced {
+                    Some(hashes) => format!("replaced {}; resign failed: {:?}", hashes, e),
+                    None => format!("resign failed: {:?}", e),
+                },
+            }
+        }
+
+        // 按顺序签名分配nonce, 签名失败的行不占用nonce. 已签名的nonce不会再次分配
+        let signed = results.iter()
+            .filter(|r| r.status == PayoutStatus::Signed)
+            .filter_map(|r| r.nonce)
+            .max();
+        let mut nonce = self.engine.pending_nonce(from).await?;
+        if let Some(signed) = signed {
+            nonce = nonce.max(signed + 1);
+        }
+        for i in remaining {
+            match self.sign(&results[i], nonce, private_key).await {
+                Ok((raw, hash)) => {
+                    let result = &mut results[i];
+                    result.nonce = Some(nonce);
+                    result.hash = Some(hash);
+                    result.status = PayoutStatus::Signed;
+                    result.message.clear();
+                    waiting.push((i, Some(raw)));
+                    nonce += U256::one();
+                }
+                Err(e) => {
+                    results[i].status = PayoutStatus::Error;
+                    results[i].message = format!("{:?}", e);
+                }
+            }
+        }
+        self.save(&results)?;
+
+        let engine = &self.engine;
+        let hashes: Vec<H256> = waiting.iter().map(|(i, _)| results[*i].hash.unwrap_or_default()).collect();
+        let mut confirmations = stream::iter(waiting.into_iter().zip(hashes))
+            .map(|((i, raw), hash)| async move {
+                if let Some(raw) = raw {
+                    engine.send_raw_transaction(raw).await?;
+                }
+                let receipt = engine.wait_transaction(hash).await?;
+                Ok::<_, crate::engine::Error>((i, receipt.status != Some(U64::zero())))
+            })
+            .buffered(self.concurrency);
+        while let Some(confirmation) = confirmations.next().await {
+            match confirmation {
+                Ok((i, success)) => {
+                    results[i].status = if success { PayoutStatus::Success } else { PayoutStatus::Failed };
+                }
+                // 广播失败后后续nonce无法上链, 停止并保存进度, 重新运行时会检查已签名的交易
+                Err(e) => {
+                    self.save(&results)?;
+                    return Err(e.into());
+                }
+            }
+            self.save(&results)?;
+        }
+        results.truncate(count);
+        Ok(results)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use serde_json::{json, Value};
+    use web3::types::{Address, H256, U256};
+    use crate::engine::Engine;
+    use crate::utils::units::Unit;
+    use crate::mock::{default_responses, MockNode};
+    use crate::payout::{load_results, parse_csv, save_results, Error, Payout, PayoutResult, PayoutStatus};
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+
+    #[test]
+    fn payout_parse_csv() {
+        let csv = "address,amount\n0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1.5\n\n# comment\n0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266, 2\n";
+        let rows = parse_csv(csv, 6).unwrap();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].amount, U256::from(1_500_000));
+        assert_eq!(rows[1].line, 5);
+
+        assert!(matches!(parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1.5", 0), Err(Error::ErrCsv(1, _))));
+        assert!(matches!(parse_csv("0x8626F6940E2eb28930eFb4CeF49B2d1F2C9C1199,1", 18), Err(Error::ErrChecksum(1, _))));
+        assert!(matches!(parse_csv("0x8626f694,1", 18), Err(Error::ErrCsv(1, _))));
+        assert!(matches!(parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,0", 18), Err(Error::ErrCsv(1, _))));
+        let duplicate = "0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1\n0x8626f6940e2eb28930efb4cef49b2d1f2c9c1199,2";
+        assert!(matches!(parse_csv(duplicate, 18), Err(Error::ErrDuplicate(_, 2, 1))));
+    }
+
+    #[test]
+    fn payout_result_file() {
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        assert!(load_results(&path).unwrap().is_empty());
+        let results = vec![
+            PayoutResult { to: Address::random(), amount: U256::exp10(18), nonce: Some(U256::from(3)), hash: Some(H256::random()), status: PayoutStatus::Success, message: String::new() },
+            PayoutResult { to: Address::random(), amount: U256::one(), nonce: None, hash: None, status: PayoutStatus::Error, message: "gas, too low".to_string() },
+        ];
+        save_results(&path, &results).unwrap();
+        assert_eq!(load_results(&path).unwrap(), results);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn payout_resume() {
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(20))));
+        let node = MockNode::start(responses).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap(), &path).concurrency(2);
+
+        let csv = "0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1.5\n0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266,2";
+        let rows = parse_csv(csv, 18).unwrap();
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert!(results.iter().all(|r| r.status == PayoutStatus::Success));
+        assert_eq!(results[0].nonce, Some(U256::from(7)));
+        assert_eq!(results[1].nonce, Some(U256::from(8)));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        // 再次运行不会重复付款
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        let rows = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1000", 18).unwrap();
+        assert!(matches!(payout.run(&rows, PRIVATE_KEY).await, Err(Error::ErrCsv(1, _))));
+        std::fs::remove_file(&path).unwrap();
+        let address = Address::from_str("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199").unwrap();
+        let rows = vec![crate::payout::PayoutRow { line: 1, to: address, amount: U256::exp10(21) }];
+        assert!(matches!(payout.run(&rows, PRIVATE_KEY).await, Err(Error::ErrInsufficientBalance { .. })));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn payout_simulation() {
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(20))));
+        let node = MockNode::start(responses).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap().with_simulation(true), &path);
+
+        let rows = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1.5", 18).unwrap();
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert_eq!(results[0].status, PayoutStatus::Success);
+        assert!(results[0].hash.is_some());
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn payout_native_fees() {
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(18))));
+        let node = MockNode::start(responses).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap().with_gas_price("1", Unit::Gwei).unwrap(), &path);
+
+        // 余额刚好等于转账金额, 不够支付手续费
+        let rows = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1", 18).unwrap();
+        match payout.run(&rows, PRIVATE_KEY).await {
+            Err(Error::ErrInsufficientBalance { required, .. }) => assert_eq!(required, U256::exp10(18) + U256::from(21_000u64) * U256::exp10(9)),
+            result => panic!("unexpected {:?}", result),
+        }
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn payout_resend_dropped() {
+        let dropped = H256::random();
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(20))));
+        responses.insert("eth_getTransactionByHash", Value::Null);
+        let node = MockNode::start_with(responses, move |method, params| {
+            (method == "eth_getTransactionReceipt" && params[0] == json!(dropped)).then_some(Value::Null)
+        }).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap(), &path);
+
+        // 第一行已用 nonce 7 签名但交易被丢弃, 节点的 pending nonce 也是 7
+        let rows = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1\n0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266,2", 18).unwrap();
+        let mut signed = PayoutResult::new(&rows[0]);
+        signed.nonce = Some(U256::from(7));
+        signed.hash = Some(dropped);
+        signed.status = PayoutStatus::Signed;
+        save_results(&path, &[signed]).unwrap();
+
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert!(results.iter().all(|r| r.status == PayoutStatus::Success));
+        assert_eq!(results[0].nonce, Some(U256::from(7)));
+        assert_ne!(results[0].hash, Some(dropped));
+        assert_eq!(results[0].message, format!("replaced {:?}", dropped));
+        assert_eq!(results[1].nonce, Some(U256::from(8)));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        // 重新签名的交易使用记录的nonce
+        let (_, hash) = payout.sign(&results[0], U256::from(7), PRIVATE_KEY).await.unwrap();
+        assert_eq!(results[0].hash, Some(hash));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn payout_nonce_used() {
+        let dropped = H256::random();
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(20))));
+        responses.insert("eth_getTransactionByHash", Value::Null);
+        // nonce 7 已被其他交易使用
+        responses.insert("eth_getTransactionCount", json!("0x8"));
+        let node = MockNode::start_with(responses, move |method, params| {
+            (method == "eth_getTransactionReceipt" && params[0] == json!(dropped)).then_some(Value::Null)
+        }).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap(), &path);
+
+        let rows = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1\n0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266,2", 18).unwrap();
+        let mut signed = PayoutResult::new(&rows[0]);
+        signed.nonce = Some(U256::from(7));
+        signed.hash = Some(dropped);
+        signed.status = PayoutStatus::Signed;
+        save_results(&path, &[signed]).unwrap();
+
+        // 不重发也不中断, 保留原交易hash, 其余行正常付款
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert_eq!(results[0].status, PayoutStatus::Review);
+        assert_eq!(results[0].hash, Some(dropped));
+        assert_eq!(results[1].status, PayoutStatus::Success);
+        assert_eq!(results[1].nonce, Some(U256::from(8)));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+        let params = node.params("eth_getTransactionCount");
+        assert!(params.iter().any(|p| p[1] == "latest"));
+
+        // 之后运行不再处理需要人工确认的行
+        let results = payout.run(&rows, PRIVATE_KEY).await.unwrap();
+        assert_eq!(results[0].status, PayoutStatus::Review);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 1);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn payout_keep_unmatched() {
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", U256::exp10(20))));
+        let node = MockNode::start(responses).await;
+        let path = std::env::temp_dir().join(format!("payout-{:x}.csv", H256::random()));
+        let payout = Payout::native(Engine::new(node.url).unwrap(), &path);
+
+        let first = parse_csv("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199,1\n0xf39fd6e51aad88f6f4ce6ab8827279cfffb92266,2", 18).unwrap();
+        payout.run(&first, PRIVATE_KEY).await.unwrap();
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+
+        // 用另一份CSV运行, 结果只包含本次的行, 之前的行仍保留在结果文件中
+        let second = parse_csv("0x70997970C51812dc3A010C7d01b50e0d17dc79C8,3", 18).unwrap();
+        let results = payout.run(&second, PRIVATE_KEY).await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(node.calls("eth_sendRawTransaction"), 3);
+        let saved = load_results(&path).unwrap();
+        assert_eq!(saved.len(), 3);
+        assert_eq!(saved[0].to, second[0].to);
+        assert_eq!(saved[1].to, first[0].to);
+
+        // 再次运行第一份CSV不会重复付款
+        let results = payout.run(&first, PRIVATE_KEY).await.unwrap();
+        assert!(results.iter().all(|r| r.status == PayoutStatus::Success));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 3);
+        assert_eq!(load_results(&path).unwrap().len(), 3);
+        std::fs::remove_file(path).unwrap();
+    }
+}
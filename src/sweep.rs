@@ -0,0 +1,223 @@
+// 归集: 把多个充值地址上的代币和原生币转到同一个地址.
+//
+// 每个地址依次处理: 先转出代币, 原生币不够付代币转账的手续费时由资金账户补足差额,
+// 最后把剩余原生币扣除手续费后全部转出. gas price 在开始时固定, 保证手续费可以精确计算.
+
+use thiserror::Error;
+use web3::types::{Address, Bytes, CallRequest, H256, U256};
+use crate::abi::erc20::Erc20ContractAbi;
+use crate::contract::ContractLiving;
+use crate::contract::erc20::{Erc20, Erc20Contract};
+use crate::engine::Engine;
+use crate::utils::units::Unit;
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("engine error")]
+    ErrEngine(#[from] crate::engine::Error),
+    #[error("contract error")]
+    ErrContract(#[from] crate::contract::Error),
+    #[error("abi error")]
+    ErrAbi(#[from] crate::abi::Error),
+    #[error("{0:?} needs {1} wei for gas but no funding key is set")]
+    ErrNoFunder(Address, U256),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepToken {
+    pub token: Address,
+    pub amount: U256,
+    pub hash: Option<H256>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct SweepReport {
+    pub address: Address,
+    // 开始归集前的原生币余额
+    pub native_balance: U256,
+    // 资金账户补充的手续费
+    pub top_up: Option<(U256, H256)>,
+    pub tokens: Vec<SweepToken>,
+    // 转出的原生币数量(已扣除手续费)
+    pub native: Option<(U256, H256)>,
+    // 出错时该地址后续步骤不再执行
+    pub error: Option<String>,
+}
+
+impl SweepReport {
+    fn new(address: Address) -> Self {
+        Self { address, native_balance: U256::zero(), top_up: None, tokens: Vec::new(), native: None, error: None }
+    }
+}
+
+pub struct Sweeper {
+    engine: Engine,
+    treasury: Address,
+    tokens: Vec<Erc20>,
+    funder: Option<String>,
+}
+
+impl Sweeper {
+    pub fn new(engine: Engine, treasury: Address) -> Self {
+        Self { engine, treasury, tokens: Vec::new(), funder: None }
+    }
+
+    pub fn token(mut self, token: Erc20) -> Self {
+        self.tokens.push(token);
+        self
+    }
+
+    // 补充手续费的资金账户私钥
+    pub fn funder(mut self, private_key: &str) -> Self {
+        self.funder = Some(private_key.to_string());
+        self
+    }
+
+    // 按顺序处理每个私钥, 单个地址出错记录在报告中并继续处理下一个
+    pub async fn run(&self, private_keys: &[&str]) -> Result<Vec<SweepReport>> {
+        let gas_price = match self.engine.gas_price() {
+            Some(gas_price) => gas_price,
+            None => self.engine.web3().eth().gas_price().await.map_err(crate::engine::Error::from)?,
+        };
+        let engine = self.engine.clone().with_gas_price(&gas_price.to_string(), Unit::Wei)?;
+
+        let mut reports = Vec::with_capacity(private_keys.len());
+        for private_key in private_keys {
+            let (address, _) = engine.parse_private_key_to_address(private_key)?;
+            let mut report = SweepReport::new(address);
+            if let Err(e) = self.sweep(&engine, gas_price, private_key, &mut report).await {
+                report.error = Some(format!("{:?}", e));
+            }
+            reports.push(report);
+        }
+        Ok(reports)
+    }
+
+    async fn sweep(&self, engine: &Engine, gas_price: U256, private_key: &str, report: &mut SweepReport) -> Result {
+        let address = report.address;
+        let eth = engine.web3().eth();
+        report.native_balance = eth.balance(address, None).await.map_err(crate::engine::Error::from)?;
+
+        let mut transfers = Vec::new();
+        let mut fee = U256::zero();
+        for token in &self.tokens {
+            let amount = token.balance_of(address).await?;
+            if amount.is_zero() {
+                continue;
+            }
+            let data = token.abi().transfer(self.treasury, amount)?;
+            let gas = estimate_gas(engine, address, *token.contract(), None, Some(data.clone())).await?;
+            fee += gas * gas_price;
+            transfers.push((*token.contract(), amount, data));
+        }
+
+        if fee > report.native_balance {
+            let missing = fee - report.native_balance;
+            let funder = self.funder.as_deref().ok_or(Error::ErrNoFunder(address, missing))?;
+            let (hash, _) = engine.send_transaction_by_value(address, missing, funder).await?;
+            engine.wait_transaction_success(hash).await?;
+            report.top_up = Some((missing, hash));
+        }
+
+        for (token, amount, data) in transfers {
+            let (hash, _) = engine.send_transaction_by_data(token, data, private_key).await?;
+            report.tokens.push(SweepToken { token, amount, hash: Some(hash) });
+            engine.wait_transaction_success(hash).await?;
+        }
+
+        let balance = eth.balance(address, None).await.map_err(crate::engine::Error::from)?;
+        if balance.is_zero() {
+            return Ok(());
+        }
+        let gas = estimate_gas(engine, address, self.treasury, Some(balance), None).await?;
+        let fee = gas * gas_price;
+        if balance > fee {
+            let value = balance - fee;
+            let (hash, _) = engine.send_transaction_by_value(self.treasury, value, private_key).await?;
+            report.native = Some((value, hash));
+            engine.wait_transaction_success(hash).await?;
+        }
+        Ok(())
+    }
+}
+
+// 估算时不带 gasPrice: 带上时节点会把gas上限限制为 (余额 - value) / gasPrice,
+// 没有原生币的地址估算代币转账, 以及转出全部余额的估算都会失败
+async fn estimate_gas(engine: &Engine, from: Address, to: Address, value: Option<U256>, data: Option<Bytes>) -> Result<U256> {
+    let request = CallRequest { from: Some(from), to: Some(to), value, data, ..Default::default() };
+    Ok(engine.web3().eth().estimate_gas(request, None).await.map_err(crate::engine::Error::from)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use web3::types::{Address, U256};
+    use crate::contract::erc20::Erc20;
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, rpc_error, MockNode};
+    use crate::sweep::{SweepToken, Sweeper};
+    use crate::utils::units::Unit;
+
+    const PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+    const FUNDER: &str = "59c6995e998f97a5a0044966f0945389dc9e86dae88c7a8412f4603b6b78690d";
+
+    async fn sweep_node(native_balance: u64) -> MockNode {
+        let mut responses = default_responses();
+        responses.insert("eth_getBalance", json!(format!("{:#x}", native_balance)));
+        // 代币余额 100
+        responses.insert("eth_call", json!(format!("0x{:064x}", 100)));
+        // 与节点一样, 带 gasPrice 且 value 为全部余额时没有余额支付gas, 估算失败
+        let balance = json!(format!("{:#x}", native_balance));
+        MockNode::start_with(responses, move |method, params| {
+            let request = &params[0];
+            let capped = !request["gasPrice"].is_null() && request["value"] == balance;
+            (method == "eth_estimateGas" && capped).then(|| rpc_error(-32000, "insufficient funds for gas * price + value", None))
+        }).await
+    }
+
+    #[tokio::test]
+    async fn sweep_exact_fee() {
+        let node = sweep_node(1_000_000).await;
+        let engine = Engine::new(node.url).unwrap().with_gas_price("2", Unit::Wei).unwrap();
+        let token = Erc20::new(engine.clone(), Address::random());
+        let treasury = Address::random();
+        let reports = Sweeper::new(engine, treasury).token(token).run(&[PRIVATE_KEY]).await.unwrap();
+
+        let report = &reports[0];
+        assert_eq!(report.error, None);
+        assert_eq!(report.top_up, None);
+        assert_eq!(report.tokens.len(), 1);
+        assert_eq!(report.tokens[0].amount, U256::from(100));
+        // 余额 - 21000 gas * 2 wei
+        assert_eq!(report.native.unwrap().0, U256::from(1_000_000 - 42_000));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+        // 转出全部余额的估算 value 为余额
+        let params = node.params("eth_estimateGas");
+        assert!(params.iter().any(|p| p[0]["value"] == format!("{:#x}", 1_000_000) && p[0]["to"] == format!("{:?}", treasury)));
+    }
+
+    #[tokio::test]
+    async fn sweep_top_up() {
+        let node = sweep_node(10_000).await;
+        let engine = Engine::new(node.url).unwrap().with_gas_price("1", Unit::Wei).unwrap();
+        let token = Erc20::new(engine.clone(), Address::random());
+
+        // 没有资金账户时记录错误, 不发送交易
+        let sweeper = Sweeper::new(engine.clone(), Address::random()).token(token);
+        let reports = sweeper.run(&[PRIVATE_KEY]).await.unwrap();
+        assert!(reports[0].error.is_some());
+        assert_eq!(node.calls("eth_sendRawTransaction"), 0);
+
+        // 没有原生币的地址估算代币转账时不能带 gasPrice
+        assert!(node.params("eth_estimateGas").iter().all(|p| p[0]["gasPrice"].is_null()));
+
+        let reports = sweeper.funder(FUNDER).run(&[PRIVATE_KEY]).await.unwrap();
+        let report = &reports[0];
+        assert_eq!(report.error, None);
+        assert_eq!(report.top_up.unwrap().0, U256::from(21_000 - 10_000));
+        assert!(matches!(report.tokens[..], [SweepToken { hash: Some(_), .. }]));
+        assert_eq!(node.calls("eth_sendRawTransaction"), 2);
+    }
+}
@@ -1,5 +1,7 @@
 use web3::types::{AccessList, Address, Bytes, H256, SignedTransaction, TransactionParameters, U256, U64};
 use serde::{Serialize, Deserialize};
+use serde_json::Value;
+use crate::utils::units::{format_units, Unit};
 
 #[derive(Serialize, Deserialize, Debug)]
 pub struct Tx {
@@ -63,4 +65,40 @@ impl Tx {
     pub fn to_json(&self) -> String {
         serde_json::to_string(self).unwrap()
     }
+
+    // 便于阅读的格式: value 以 ether 显示, gas price 相关字段以 gwei 显示
+    pub fn to_json_pretty(&self) -> String {
+        let mut json = serde_json::to_value(self).unwrap();
+        let fields = [
+            ("value", Some(self.value), Unit::Ether),
+            ("gas_price", self.gas_price, Unit::Gwei),
+            ("max_fee_per_gas", self.max_fee_per_gas, Unit::Gwei),
+            ("max_priority_fee_per_gas", self.max_priority_fee_per_gas, Unit::Gwei),
+        ];
+        for (name, value, unit) in fields {
+            if let Some(value) = value {
+                json[name] = Value::String(format!("{} {}", format_units(value, unit).unwrap_or_default(), unit));
+            }
+        }
+        serde_json::to_string_pretty(&json).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::{TransactionParameters, U256};
+    use crate::tx::Tx;
+
+    #[test]
+    fn tx_to_json_pretty() {
+        let tx = Tx::from_transaction_parameters(TransactionParameters {
+            value: U256::exp10(15) * 15,
+            gas_price: Some(U256::from(1_500_000_000u64)),
+            ..Default::default()
+        });
+        let json: serde_json::Value = serde_json::from_str(&tx.to_json_pretty()).unwrap();
+        assert_eq!(json["value"], "0.015 ether");
+        assert_eq!(json["gas_price"], "1.5 gwei");
+        assert_eq!(json["max_fee_per_gas"], serde_json::Value::Null);
+    }
 }
\ No newline at end of file
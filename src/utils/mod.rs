@@ -0,0 +1,169 @@
+pub mod units;
+
+use web3::signing::{keccak256};
+use web3::types::{Address, H256, U256};
+
+pub trait AddressUtils {
+    // 将地址转换为checksum_address
+    fn checksum_address(&self) -> String;
+}
+
+impl AddressUtils for Address {
+    fn checksum_address(&self) -> String {
+        // 0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199
+        let lowercase_address = hex::encode(self);
+        let hash_address = hex::encode(keccak256(lowercase_address.as_bytes()));
+        let mut checksum_address = "0x".to_string();
+        for c in 0..40 {
+            let ch = match &hash_address[c..=c] {
+                "0" | "1" | "2" | "3" | "4" | "5" | "6" | "7" => {
+                    lowercase_address[c..=c].to_lowercase()
+                }
+                _ => lowercase_address.to_string()[c..=c].to_uppercase(),
+            };
+            checksum_address.push_str(&ch);
+        }
+        checksum_address
+    }
+}
+
+pub trait ToHex {
+    fn to_hex(&self) -> String;
+}
+
+impl ToHex for Address {
+    fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self))
+    }
+}
+
+impl ToHex for H256  {
+    fn to_hex(&self) -> String {
+        format!("0x{}", hex::encode(self))
+    }
+}
+
+// CREATE 部署的合约地址: keccak256(rlp([deployer, nonce]))[12..]
+pub fn create_address(deployer: Address, nonce: U256) -> Address {
+    let mut nonce_bytes = [0u8; 32];
+    nonce.to_big_endian(&mut nonce_bytes);
+    let nonce_bytes = &nonce_bytes[(32 - nonce.bits().div_ceil(8))..];
+
+    let mut item = vec![0x94];
+    item.extend_from_slice(deployer.as_bytes());
+    match nonce_bytes {
+        [] => item.push(0x80),
+        [b] if *b < 0x80 => item.push(*b),
+        bytes => {
+            item.push(0x80 + bytes.len() as u8);
+            item.extend_from_slice(bytes);
+        }
+    }
+
+    let mut stream = vec![0xc0 + item.len() as u8];
+    stream.extend(item);
+    Address::from_slice(&keccak256(&stream)[12..])
+}
+
+// CREATE2 部署的合约地址: keccak256(0xff ++ deployer ++ salt ++ keccak256(init_code))[12..]
+pub fn create2_address(deployer: Address, salt: H256, init_code_hash: H256) -> Address {
+    let mut stream = vec![0xff];
+    stream.extend_from_slice(deployer.as_bytes());
+    stream.extend_from_slice(salt.as_bytes());
+    stream.extend_from_slice(init_code_hash.as_bytes());
+    Address::from_slice(&keccak256(&stream)[12..])
+}
+
+pub fn create2_address_from_code(deployer: Address, salt: H256, init_code: &[u8]) -> Address {
+    create2_address(deployer, salt, H256::from(keccak256(init_code)))
+}
+
+// 函数选择器: keccak256("transfer(address,uint256)")[..4]
+pub fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+// solidity 的函数分发会以 PUSHn selector 的形式出现在字节码中, 高位为0的选择器会被编译为更短的PUSH
+pub fn code_has_selector(code: &[u8], selector: [u8; 4]) -> bool {
+    let trimmed: Vec<u8> = selector.iter().copied().skip_while(|b| *b == 0).collect();
+    if trimmed.is_empty() {
+        return false;
+    }
+    let mut pattern = vec![0x5f + trimmed.len() as u8];
+    pattern.extend(trimmed);
+    code.windows(pattern.len()).any(|w| w == pattern.as_slice())
+}
+
+// EIP-1167 最小代理的运行时字节码: 363d3d373d3d3d363d73 <20字节实现地址> 5af43d82803e903d91602b57fd5bf3
+pub fn minimal_proxy_target(code: &[u8]) -> Option<Address> {
+    const PREFIX: [u8; 10] = [0x36, 0x3d, 0x3d, 0x37, 0x3d, 0x3d, 0x3d, 0x36, 0x3d, 0x73];
+    const SUFFIX: [u8; 15] = [0x5a, 0xf4, 0x3d, 0x82, 0x80, 0x3e, 0x90, 0x3d, 0x91, 0x60, 0x2b, 0x57, 0xfd, 0x5b, 0xf3];
+    if code.len() != PREFIX.len() + 20 + SUFFIX.len() || !code.starts_with(&PREFIX) || !code.ends_with(&SUFFIX) {
+        return None;
+    }
+    Some(Address::from_slice(&code[PREFIX.len()..PREFIX.len() + 20]))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+    use web3::types::{Address, H256, U256};
+    use crate::utils::{code_has_selector, create2_address_from_code, create_address, minimal_proxy_target, selector, ToHex};
+    use super::AddressUtils;
+
+    #[test]
+    fn address_utils() {
+        let addr = Address::from_str("0x8626f6940E2eb28930eFb4CeF49B2d1F2C9C1199").unwrap();
+        println!("{}", addr.to_hex());
+        println!("{}", addr.checksum_address())
+    }
+
+    #[test]
+    fn to_hex() {
+        let hash = H256::random();
+        println!("{}", hash.to_hex())
+    }
+
+    #[test]
+    fn create_address_from_nonce() {
+        let deployer = Address::from_str("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0").unwrap();
+        assert_eq!(create_address(deployer, U256::zero()), Address::from_str("0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d").unwrap());
+        assert_eq!(create_address(deployer, U256::one()), Address::from_str("0x343c43a37d37dff08ae8c4a11544c718abb4fcf8").unwrap());
+        assert_eq!(create_address(deployer, U256::from(2)), Address::from_str("0xf778b86fa74e846c4f0a1fbd1335fe81c00a0c91").unwrap());
+        assert_eq!(create_address(deployer, U256::from(3)), Address::from_str("0xfffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c").unwrap());
+    }
+
+    #[test]
+    fn create2_address() {
+        // EIP-1014 示例
+        let deployer = Address::zero();
+        assert_eq!(create2_address_from_code(deployer, H256::zero(), &[0x00]), Address::from_str("0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38").unwrap());
+        let deployer = Address::from_str("0xdeadbeef00000000000000000000000000000000").unwrap();
+        assert_eq!(create2_address_from_code(deployer, H256::zero(), &[0x00]), Address::from_str("0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3").unwrap());
+        let deployer = Address::from_str("0x00000000000000000000000000000000deadbeef").unwrap();
+        let salt = H256::from_str("0x00000000000000000000000000000000000000000000000000000000cafebabe").unwrap();
+        let init_code = hex::decode("deadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeefdeadbeef").unwrap();
+        assert_eq!(create2_address_from_code(deployer, salt, &init_code), Address::from_str("0x1d8bfDC5D46DC4f61D6b6115972536eBE6A8854C").unwrap());
+    }
+
+    #[test]
+    fn selector_in_code() {
+        assert_eq!(hex::encode(selector("transfer(address,uint256)")), "a9059cbb");
+        // DUP1 PUSH4 0x40c10f19(mint) EQ PUSH2 0x0114 JUMPI DUP1 PUSH4 0x70a08231(balanceOf) EQ
+        let code = hex::decode("806340c10f191461011457806370a0823114").unwrap();
+        assert!(code_has_selector(&code, selector("mint(address,uint256)")));
+        assert!(code_has_selector(&code, selector("balanceOf(address)")));
+        assert!(!code_has_selector(&code, selector("pause()")));
+        assert!(code_has_selector(&hex::decode("8060011461").unwrap(), [0, 0, 0, 1]));
+    }
+
+    #[test]
+    fn minimal_proxy() {
+        let target = Address::from_str("0xbebebebebebebebebebebebebebebebebebebebe").unwrap();
+        let code = hex::decode("363d3d373d3d3d363d73bebebebebebebebebebebebebebebebebebebebe5af43d82803e903d91602b57fd5bf3").unwrap();
+        assert_eq!(minimal_proxy_target(&code), Some(target));
+        assert_eq!(minimal_proxy_target(&code[..code.len() - 1]), None);
+        assert_eq!(minimal_proxy_target(&hex::decode("806340c10f1914").unwrap()), None);
+    }
+}
@@ -0,0 +1,202 @@
+// 以太坊单位换算: "1.5" gwei <-> 1500000000 wei
+
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use web3::types::U256;
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+// U256 最多表示 77 位十进制数
+pub const MAX_DECIMALS: u8 = 77;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum Error {
+    #[error("invalid number: {0}")]
+    ErrParse(String),
+    #[error("too many fractional digits: {0}, decimals {1}")]
+    ErrPrecision(usize, u8),
+    #[error("unknown unit: {0}")]
+    ErrUnit(String),
+    #[error("number overflow")]
+    ErrOverflow,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Wei,
+    Kwei,
+    Mwei,
+    Gwei,
+    Szabo,
+    Finney,
+    Ether,
+}
+
+impl Unit {
+    pub fn decimals(&self) -> u8 {
+        match self {
+            Unit::Wei => 0,
+            Unit::Kwei => 3,
+            Unit::Mwei => 6,
+            Unit::Gwei => 9,
+            Unit::Szabo => 12,
+            Unit::Finney => 15,
+            Unit::Ether => 18,
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Unit::Wei => "wei",
+            Unit::Kwei => "kwei",
+            Unit::Mwei => "mwei",
+            Unit::Gwei => "gwei",
+            Unit::Szabo => "szabo",
+            Unit::Finney => "finney",
+            Unit::Ether => "ether",
+        }
+    }
+}
+
+impl FromStr for Unit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Ok(match s.to_lowercase().as_str() {
+            "wei" => Unit::Wei,
+            "kwei" | "babbage" => Unit::Kwei,
+            "mwei" | "lovelace" => Unit::Mwei,
+            "gwei" | "shannon" => Unit::Gwei,
+            "szabo" | "microether" => Unit::Szabo,
+            "finney" | "milliether" => Unit::Finney,
+            "ether" | "eth" => Unit::Ether,
+            _ => return Err(Error::ErrUnit(s.to_string())),
+        })
+    }
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.name())
+    }
+}
+
+// 单位可以是 Unit, 单位名称("gwei") 或者小数位数(18)
+pub trait Units {
+    fn decimals(&self) -> Result<u8>;
+}
+
+impl Units for Unit {
+    fn decimals(&self) -> Result<u8> {
+        Ok(Unit::decimals(self))
+    }
+}
+
+impl Units for &str {
+    fn decimals(&self) -> Result<u8> {
+        Ok(Unit::from_str(self)?.decimals())
+    }
+}
+
+impl Units for u8 {
+    fn decimals(&self) -> Result<u8> {
+        if *self > MAX_DECIMALS {
+            return Err(Error::ErrUnit(self.to_string()));
+        }
+        Ok(*self)
+    }
+}
+
+// parse_units("1.5", "gwei") = 1500000000, 小数位数超过单位精度时报错而不是截断
+pub fn parse_units<U: Units>(value: &str, unit: U) -> Result<U256> {
+    let decimals = unit.decimals()?;
+    let err = || Error::ErrParse(value.to_string());
+    let (integer, fraction) = match value.trim().split_once('.') {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (value.trim(), ""),
+    };
+    if integer.is_empty() && fraction.is_empty() {
+        return Err(err());
+    }
+    if !integer.chars().chain(fraction.chars()).all(|c| c.is_ascii_digit()) {
+        return Err(err());
+    }
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > decimals as usize {
+        return Err(Error::ErrPrecision(fraction.len(), decimals));
+    }
+
+    let integer = if integer.is_empty() { U256::zero() } else { U256::from_dec_str(integer).map_err(|_| Error::ErrOverflow)? };
+    let fraction = if fraction.is_empty() {
+        U256::zero()
+    } else {
+        U256::from_dec_str(fraction).map_err(|_| err())? * U256::exp10(decimals as usize - fraction.len())
+    };
+    integer.checked_mul(U256::exp10(decimals as usize))
+        .and_then(|value| value.checked_add(fraction))
+        .ok_or(Error::ErrOverflow)
+}
+
+// format_units(1500000000, "gwei") = "1.5", 去掉小数末尾的0
+pub fn format_units<U: Units>(value: U256, unit: U) -> Result<String> {
+    let decimals = unit.decimals()? as usize;
+    let digits = format!("{:0>width$}", value.to_string(), width = decimals + 1);
+    let (integer, fraction) = digits.split_at(digits.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        Ok(integer.to_string())
+    } else {
+        Ok(format!("{}.{}", integer, fraction))
+    }
+}
+
+pub fn parse_ether(value: &str) -> Result<U256> {
+    parse_units(value, Unit::Ether)
+}
+
+pub fn format_ether(value: U256) -> String {
+    format_units(value, Unit::Ether).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use web3::types::U256;
+    use crate::utils::units::{format_ether, format_units, parse_ether, parse_units, Error, Unit};
+
+    #[test]
+    fn units_parse() {
+        assert_eq!(parse_units("1.5", "gwei").unwrap(), U256::from(1_500_000_000u64));
+        assert_eq!(parse_units("1", Unit::Ether).unwrap(), U256::exp10(18));
+        assert_eq!(parse_units("0.001", 6u8).unwrap(), U256::from(1000));
+        assert_eq!(parse_units("2", "Finney").unwrap(), U256::exp10(15) * 2);
+        assert_eq!(parse_units("3", "szabo").unwrap(), U256::exp10(12) * 3);
+        assert_eq!(parse_units("7", "kwei").unwrap(), U256::from(7000));
+        assert_eq!(parse_units("42", "wei").unwrap(), U256::from(42));
+        assert_eq!(parse_ether("0.1").unwrap(), U256::exp10(17));
+
+        assert_eq!(parse_units("1.5", "wei"), Err(Error::ErrPrecision(1, 0)));
+        assert_eq!(parse_units("0.0000000001", "gwei"), Err(Error::ErrPrecision(10, 9)));
+        assert_eq!(parse_units("1", "lamport"), Err(Error::ErrUnit("lamport".to_string())));
+        assert_eq!(parse_units("1", 78u8), Err(Error::ErrUnit("78".to_string())));
+        assert_eq!(parse_units(&"9".repeat(70), "ether"), Err(Error::ErrOverflow));
+        assert!(matches!(parse_units("1e18", "wei"), Err(Error::ErrParse(_))));
+
+        // 不接受分隔符, 避免 "1,5" 这类不同地区写法被误读
+        for value in ["1,5", "1,000", "1,000.5", "1_000", "1_0_0", "0.1_5", " 1 000"] {
+            assert!(matches!(parse_ether(value), Err(Error::ErrParse(_))), "{}", value);
+        }
+    }
+
+    #[test]
+    fn units_format() {
+        assert_eq!(format_units(U256::from(1_500_000_000u64), "gwei").unwrap(), "1.5");
+        assert_eq!(format_units(U256::exp10(18), 18u8).unwrap(), "1");
+        assert_eq!(format_units(U256::from(1), Unit::Ether).unwrap(), "0.000000000000000001");
+        assert_eq!(format_units(U256::zero(), Unit::Gwei).unwrap(), "0");
+        assert_eq!(format_ether(U256::exp10(15) * 1234), "1.234");
+        assert_eq!(format_units(U256::MAX, 0u8).unwrap(), U256::MAX.to_string());
+        let value = U256::from_dec_str("123456789012345678901").unwrap();
+        assert_eq!(parse_ether(&format_ether(value)).unwrap(), value);
+    }
+}
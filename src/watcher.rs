@@ -0,0 +1,616 @@
+// 充值监听: 跟踪一组地址, 扫描已确认区块中的原生币转账和ERC20 Transfer事件
+//
+// 只处理达到确认数的区块, 处理进度(游标)在每个区块处理完成后写入本地文件, 重启后从游标继续.
+// 最近处理过的区块hash会一并保存, 如果新区块的 parent_hash 与之不符(回滚深度超过确认数),
+// 会逐个回退已处理的区块并通知回调, 然后重新扫描.
+
+use std::collections::{HashSet, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
+use serde::{Deserialize, Serialize};
+use thiserror::Error;
+use web3::types::{Address, Block, BlockId, BlockNumber, FilterBuilder, Transaction, H256, U256, U64};
+use crate::abi::ContractAbi;
+use crate::abi::erc20::{Erc20Abi, Erc20ContractAbi, Erc20Event};
+use crate::engine::Engine;
+
+pub type Result<T = ()> = std::result::Result<T, Error>;
+
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("web3 error")]
+    ErrWeb3(#[from] web3::Error),
+    #[error("engine error")]
+    ErrEngine(#[from] crate::engine::Error),
+    #[error("abi error")]
+    ErrAbi(#[from] crate::abi::Error),
+    #[error("io error")]
+    ErrIo(#[from] std::io::Error),
+    #[error("json error")]
+    ErrJson(#[from] serde_json::Error),
+    #[error("reorg deeper than saved history at block {0}")]
+    ErrReorgTooDeep(u64),
+}
+
+impl Error {
+    // 游标读写失败和过深的回滚需要人工处理, 其他错误(超时、502、限流等)重试即可
+    fn is_fatal(&self) -> bool {
+        matches!(self, Error::ErrIo(_) | Error::ErrJson(_) | Error::ErrReorgTooDeep(_))
+    }
+}
+
+// 保存的区块hash数量, 回滚深度超过它时无法自动恢复
+const HISTORY_SIZE: usize = 128;
+
+// 连续出错时重试间隔逐次翻倍, 最长等待时间
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deposit {
+    // None 为原生币
+    pub token: Option<Address>,
+    pub from: Address,
+    pub to: Address,
+    pub value: U256,
+    pub transaction_hash: H256,
+    pub block_number: u64,
+    pub block_hash: H256,
+    pub log_index: Option<U256>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DepositEvent {
+    Deposit(Deposit),
+    // 已通知过的区块被回滚, 该区块内的充值需要作废
+    Reorg { block_number: u64, block_hash: H256 },
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct Cursor {
+    next_block: u64,
+    history: VecDeque<(u64, H256)>,
+}
+
+impl Cursor {
+    fn load(path: &PathBuf) -> Result<Option<Self>> {
+        match std::fs::read(path) {
+            Ok(data) => Ok(Some(serde_json::from_slice(&data)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    // 先写临时文件再重命名, 避免写入一半时崩溃导致游标损坏
+    fn save(&self, path: &PathBuf) -> Result {
+        let tmp = path.with_extension("tmp");
+        std::fs::write(&tmp, serde_json::to_vec(self)?)?;
+        std::fs::rename(tmp, path)?;
+        Ok(())
+    }
+
+    fn push(&mut self, number: u64, hash: H256) {
+        self.history.push_back((number, hash));
+        while self.history.len() > HISTORY_SIZE {
+            self.history.pop_front();
+        }
+        self.next_block = number + 1;
+    }
+}
+
+pub struct DepositWatcher {
+    engine: Engine,
+    abi: Erc20Abi,
+    addresses: HashSet<Address>,
+    tokens: Vec<Address>,
+    confirmations: u64,
+    start_block: Option<u64>,
+    poll_interval: Duration,
+    cursor_path: PathBuf,
+    cursor: Option<Cursor>,
+}
+
+impl DepositWatcher {
+    pub fn new(engine: Engine, cursor_path: impl Into<PathBuf>) -> Self {
+        Self {
+            engine,
+            abi: Erc20Abi::new(),
+            addresses: HashSet::new(),
+            tokens: Vec::new(),
+            confirmations: 12,
+            start_block: None,
+            poll_interval: Duration::from_secs(3),
+            cursor_path: cursor_path.into(),
+            cursor: None,
+        }
+    }
+
+    pub fn confirmations(mut self, confirmations: u64) -> Self {
+        self.confirmations = confirmations;
+        self
+    }
+
+    // 没有游标文件时的起始区块, 不设置则从当前已确认的区块开始
+    pub fn start_block(mut self, block: u64) -> Self {
+        self.start_block = Some(block);
+        self
+    }
+
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    pub fn add_address(&mut self, address: Address) {
+        self.addresses.insert(address);
+    }
+
+    pub fn remove_address(&mut self, address: &Address) {
+        self.addresses.remove(address);
+    }
+
+    pub fn add_token(&mut self, token: Address) {
+        if !self.tokens.contains(&token) {
+            self.tokens.push(token);
+        }
+    }
+
+    // 持续监听, 每次处理完所有已确认的区块后等待 poll_interval.
+    // 节点错误按退避间隔重试, 只有游标读写失败或回滚过深时返回
+    pub async fn run<F: FnMut(DepositEvent) + Send>(&mut self, mut callback: F) -> Result {
+        let mut backoff = self.poll_interval;
+        loop {
+            match self.poll(&mut callback).await {
+                Ok(_) => {
+                    backoff = self.poll_interval;
+                    tokio::time::sleep(self.poll_interval).await;
+                }
+                Err(e) if e.is_fatal() => return Err(e),
+                Err(_) => {
+                    tokio::time::sleep(backoff).await;
+                    backoff = (backoff * 2).min(MAX_BACKOFF.max(self.poll_interval));
+                }
+            }
+        }
+    }
+
+    // 处理当前所有已确认的区块, 返回处理的区块数量
+    pub async fn poll<F: FnMut(DepositEvent) + Send>(&mut self, callback: &mut F) -> Result<u64> {
+        let head = self.engine.block_number().await?;
+        let confirmed = match head.checked_sub(self.confirmations) {
+            Some(confirmed) => confirmed,
+            None => return Ok(0),
+        };
+
+        let mut cursor = match self.cursor.take() {
+            Some(cursor) => cursor,
+            None => Cursor::load(&self.cursor_path)?.unwrap_or(Cursor {
+                next_block: self.start_block.unwrap_or(confirmed),
+                history: VecDeque::new(),
+            }),
+        };
+
+        let mut processed = 0;
+        let result = loop {
+            if cursor.next_block > confirmed {
+                break Ok(processed);
+            }
+            match self.process_block(&mut cursor, callback).await {
+                Ok(true) => processed += 1,
+                // 节点还没有这个区块(负载均衡后面的节点高度不一致), 等下一轮
+                Ok(false) => break Ok(processed),
+                Err(e) => break Err(e),
+            }
+        };
+        self.cursor = Some(cursor);
+        result
+    }
+
+    // 返回 false 表示区块还不可用, 游标没有变化
+    async fn process_block<F: FnMut(DepositEvent) + Send>(&self, cursor: &mut Cursor, callback: &mut F) -> Result<bool> {
+        let number = cursor.next_block;
+        let block = match self.engine.web3().eth().block_with_txs(BlockId::Number(BlockNumber::Number(U64::from(number)))).await? {
+            Some(block) => block,
+            None => return Ok(false),
+        };
+        let hash = block.hash.unwrap_or_default();
+
+        if let Some(&(last_number, last_hash)) = cursor.history.back() {
+            if last_number + 1 == number && last_hash != block.parent_hash {
+                // 上一个已处理的区块不在当前链上, 回退一个区块后重新检查.
+                // 只剩一个区块时无法确认更早的区块是否也被回滚, 游标保持不变
+                if cursor.history.len() == 1 {
+                    return Err(Error::ErrReorgTooDeep(last_number));
+                }
+                cursor.history.pop_back();
+                cursor.next_block = last_number;
+                cursor.save(&self.cursor_path)?;
+                callback(DepositEvent::Reorg { block_number: last_number, block_hash: last_hash });
+                return Ok(true);
+            }
+        }
+
+        let mut deposits = Vec::new();
+        for (transaction_hash, deposit) in native_deposits(&block, &self.addresses) {
+            // 失败的交易不会转移资金
+            let receipt = self.engine.web3().eth().transaction_receipt(transaction_hash).await?;
+            if receipt.map(|r| r.status != Some(U64::zero())).unwrap_or(false) {
+                deposits.push(deposit);
+            }
+        }
+        deposits.extend(self.token_deposits(number, hash).await?);
+
+        for deposit in deposits {
+            callback(DepositEvent::Deposit(deposit));
+        }
+        cursor.push(number, hash);
+        cursor.save(&self.cursor_path)?;
+        Ok(true)
+    }
+
+    async fn token_deposits(&self, number: u64, hash: H256) -> Result<Vec<Deposit>> {
+        if self.tokens.is_empty() || self.addresses.is_empty() {
+            return Ok(Vec::new());
+        }
+        let filter = FilterBuilder::default()
+            .block_hash(hash)
+            .address(self.tokens.clone())
+            .topics(
+                Some(vec![self.abi.event_topic("Transfer")?]),
+                None,
+                Some(self.addresses.iter().map(|a| H256::from(*a)).collect()),
+                None,
+            )
+            .build();
+        let mut logs = self.engine.web3().eth().logs(filter).await?;
+        logs.sort_by_key(|log| log.log_index);
+
+        Ok(logs.iter().filter_map(|log| match self.abi.decode_log(log) {
+            Ok(Erc20Event::Transfer(transfer)) if self.addresses.contains(&transfer.to) => Some(Deposit {
+                token: Some(log.address),
+                from: transfer.from,
+                to: transfer.to,
+                value: transfer.value,
+                transaction_hash: log.transaction_hash.unwrap_or_default(),
+                block_number: number,
+                block_hash: hash,
+                log_index: log.log_index,
+            }),
+            _ => None,
+        }).collect())
+    }
+}
+
+fn native_deposits(block: &Block<Transaction>, addresses: &HashSet<Address>) -> Vec<(H256, Deposit)> {
+    block.transactions.iter()
+        .filter(|tx| !tx.value.is_zero() && tx.to.map(|to| addresses.contains(&to)).unwrap_or(false))
+        .map(|tx| (tx.hash, Deposit {
+            token: None,
+            from: tx.from.unwrap_or_default(),
+            to: tx.to.unwrap_or_default(),
+            value: tx.value,
+            transaction_hash: tx.hash,
+            block_number: block.number.map(|n| n.as_u64()).unwrap_or_default(),
+            block_hash: block.hash.unwrap_or_default(),
+            log_index: None,
+        }))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::path::PathBuf;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use serde_json::{json, Value};
+    use web3::types::{Address, Block, Transaction, H256, U256, U64};
+    use crate::abi::ContractAbi;
+    use crate::abi::erc20::Erc20Abi;
+    use crate::engine::Engine;
+    use crate::mock::{default_responses, rpc_error, MockNode};
+    use crate::watcher::{native_deposits, Cursor, Deposit, DepositEvent, DepositWatcher, Error, HISTORY_SIZE};
+
+    // 模拟链: hashes[n] 为区块 n 的hash, 超过 hashes 长度的区块节点返回 null
+    #[derive(Default)]
+    struct Chain {
+        head: u64,
+        hashes: Vec<H256>,
+        // 区块号 -> 其中的原生币转账
+        transactions: Vec<(u64, Transaction)>,
+        logs: Vec<Value>,
+    }
+
+    impl Chain {
+        fn new(head: u64, len: u64) -> Self {
+            Self { head, hashes: (0..len).map(|_| H256::random()).collect(), ..Default::default() }
+        }
+
+        fn block(&self, number: u64) -> Value {
+            let hash = match self.hashes.get(number as usize) {
+                Some(hash) => *hash,
+                None => return Value::Null,
+            };
+            let block = Block {
+                hash: Some(hash),
+                parent_hash: number.checked_sub(1).map(|n| self.hashes[n as usize]).unwrap_or_default(),
+                number: Some(U64::from(number)),
+                transactions: self.transactions.iter().filter(|(n, _)| *n == number).map(|(_, tx)| tx.clone()).collect(),
+                ..Default::default()
+            };
+            serde_json::to_value(block).unwrap()
+        }
+    }
+
+    async fn chain_node(chain: Arc<Mutex<Chain>>) -> MockNode {
+        MockNode::start_with(default_responses(), move |method, params| {
+            let chain = chain.lock().unwrap();
+            match method {
+                "eth_blockNumber" => Some(json!(format!("{:#x}", chain.head))),
+                "eth_getBlockByNumber" => {
+                    let number = u64::from_str_radix(params[0].as_str()?.trim_start_matches("0x"), 16).ok()?;
+                    Some(chain.block(number))
+                }
+                "eth_getLogs" => Some(json!(chain.logs.iter().filter(|log| log["blockHash"] == params[0]["blockHash"]).collect::<Vec<_>>())),
+                _ => None,
+            }
+        }).await
+    }
+
+    fn cursor_path() -> PathBuf {
+        std::env::temp_dir().join(format!("cursor-{:x}.json", H256::random()))
+    }
+
+    async fn poll(watcher: &mut DepositWatcher) -> (crate::watcher::Result<u64>, Vec<DepositEvent>) {
+        let mut events = Vec::new();
+        let result = watcher.poll(&mut |event| events.push(event)).await;
+        (result, events)
+    }
+
+    #[test]
+    fn watcher_native_deposits() {
+        let ours = Address::random();
+        let mut addresses = HashSet::new();
+        addresses.insert(ours);
+
+        let deposit = Transaction { hash: H256::random(), from: Some(Address::random()), to: Some(ours), value: U256::exp10(18), ..Default::default() };
+        let zero_value = Transaction { hash: H256::random(), to: Some(ours), ..Default::default() };
+        let other = Transaction { hash: H256::random(), to: Some(Address::random()), value: U256::one(), ..Default::default() };
+        let block = Block { hash: Some(H256::random()), number: Some(U64::from(10)), transactions: vec![deposit.clone(), zero_value, other], ..Default::default() };
+
+        let deposits = native_deposits(&block, &addresses);
+        assert_eq!(deposits.len(), 1);
+        assert_eq!(deposits[0].0, deposit.hash);
+        assert_eq!(deposits[0].1.to, ours);
+        assert_eq!(deposits[0].1.value, U256::exp10(18));
+        assert_eq!(deposits[0].1.block_number, 10);
+        assert_eq!(deposits[0].1.token, None);
+    }
+
+    #[test]
+    fn watcher_cursor() {
+        let path = std::env::temp_dir().join(format!("cursor-{:x}.json", H256::random()));
+        assert_eq!(Cursor::load(&path).unwrap(), None);
+
+        let mut cursor = Cursor::default();
+        for n in 0..(HISTORY_SIZE as u64 + 10) {
+            cursor.push(n, H256::from_low_u64_be(n));
+        }
+        assert_eq!(cursor.history.len(), HISTORY_SIZE);
+        assert_eq!(cursor.next_block, HISTORY_SIZE as u64 + 10);
+
+        cursor.save(&path).unwrap();
+        assert_eq!(Cursor::load(&path).unwrap(), Some(cursor));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_confirmations() {
+        let ours = Address::random();
+        let deposit = Transaction { hash: H256::random(), from: Some(Address::random()), to: Some(ours), value: U256::exp10(18), ..Default::default() };
+        let mut chain = Chain::new(10, 20);
+        chain.transactions.push((6, deposit.clone()));
+        chain.transactions.push((9, deposit.clone()));
+        let chain = Arc::new(Mutex::new(chain));
+        let node = chain_node(chain.clone()).await;
+
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(3).start_block(5);
+        watcher.add_address(ours);
+
+        // 区块 10 只有 3 个确认之后的区块 5..=7 被处理
+        let (result, events) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], DepositEvent::Deposit(Deposit { block_number: 6, token: None, value, .. }) if *value == U256::exp10(18)));
+
+        let (result, events) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 0);
+        assert!(events.is_empty());
+
+        // 游标写入文件, 新的 watcher 从区块 8 继续
+        chain.lock().unwrap().head = 12;
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(3);
+        watcher.add_address(ours);
+        let (result, events) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 2);
+        assert!(matches!(&events[..], [DepositEvent::Deposit(Deposit { block_number: 9, .. })]));
+        assert_eq!(node.calls("eth_getBlockByNumber"), 5);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_block_not_available() {
+        // 节点报告的高度为 10, 但只能取到区块 0..=6
+        let chain = Arc::new(Mutex::new(Chain::new(10, 7)));
+        let node = chain_node(chain.clone()).await;
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(2).start_block(5);
+
+        let (result, _) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 2);
+        // 区块 7 返回 null 后结束本轮, 不重复请求
+        assert_eq!(node.calls("eth_getBlockByNumber"), 3);
+
+        let (result, _) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 0);
+        assert_eq!(node.calls("eth_getBlockByNumber"), 4);
+
+        let hash = H256::random();
+        chain.lock().unwrap().hashes.push(hash);
+        let (result, _) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 1);
+        assert_eq!(Cursor::load(&path).unwrap().unwrap().history.back(), Some(&(7, hash)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_reorg() {
+        let chain = Arc::new(Mutex::new(Chain::new(4, 5)));
+        let node = chain_node(chain.clone()).await;
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(0).start_block(0);
+        assert_eq!(poll(&mut watcher).await.0.unwrap(), 5);
+
+        // 区块 3 和 4 被替换, 链增长到 5
+        let old: Vec<H256> = chain.lock().unwrap().hashes[3..].to_vec();
+        {
+            let mut chain = chain.lock().unwrap();
+            chain.hashes.truncate(3);
+            chain.hashes.extend((0..3).map(|_| H256::random()));
+            chain.head = 5;
+        }
+        let (result, events) = poll(&mut watcher).await;
+        assert_eq!(events, vec![
+            DepositEvent::Reorg { block_number: 4, block_hash: old[1] },
+            DepositEvent::Reorg { block_number: 3, block_hash: old[0] },
+        ]);
+        // 回退两个区块, 重新处理 3..=5
+        assert_eq!(result.unwrap(), 5);
+        let hashes = chain.lock().unwrap().hashes.clone();
+        let history: Vec<(u64, H256)> = Cursor::load(&path).unwrap().unwrap().history.into_iter().collect();
+        assert_eq!(history, (0..6).map(|n| (n, hashes[n as usize])).collect::<Vec<_>>());
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_reorg_too_deep() {
+        let chain = Arc::new(Mutex::new(Chain::new(3, 4)));
+        let node = chain_node(chain.clone()).await;
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(0);
+        assert_eq!(poll(&mut watcher).await.0.unwrap(), 1);
+
+        // 唯一保存的区块 3 被回滚, 无法判断回滚深度
+        {
+            let mut chain = chain.lock().unwrap();
+            chain.hashes[3] = H256::random();
+            chain.hashes.push(H256::random());
+            chain.head = 4;
+        }
+        let saved = Cursor::load(&path).unwrap().unwrap();
+        for _ in 0..2 {
+            let (result, events) = poll(&mut watcher).await;
+            assert!(matches!(result, Err(Error::ErrReorgTooDeep(3))));
+            assert!(events.is_empty());
+            assert_eq!(watcher.cursor.as_ref(), Some(&saved));
+        }
+        assert_eq!(Cursor::load(&path).unwrap().unwrap(), saved);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_token_deposits() {
+        let (ours, token, sender) = (Address::random(), Address::random(), Address::random());
+        let chain = Arc::new(Mutex::new(Chain::new(2, 3)));
+        let transfer = Erc20Abi::new().event_topic("Transfer").unwrap();
+        let block_hash = chain.lock().unwrap().hashes[2];
+        let log = |index: u64, to: Address, value: u64| json!({
+            "address": token,
+            "topics": [transfer, H256::from(sender), H256::from(to)],
+            "data": format!("0x{:064x}", value),
+            "blockHash": block_hash,
+            "transactionHash": H256::repeat_byte(index as u8),
+            "logIndex": format!("{:#x}", index),
+        });
+        chain.lock().unwrap().logs = vec![log(5, ours, 200), log(1, ours, 100), log(3, Address::random(), 300)];
+        let node = chain_node(chain).await;
+
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path).confirmations(0);
+        watcher.add_address(ours);
+        watcher.add_token(token);
+        let (result, events) = poll(&mut watcher).await;
+        assert_eq!(result.unwrap(), 1);
+        // 按 log_index 排序, 只保留转入监听地址的转账
+        let deposits: Vec<(U256, Option<U256>)> = events.iter().map(|event| match event {
+            DepositEvent::Deposit(deposit) => {
+                assert_eq!(deposit.token, Some(token));
+                assert_eq!((deposit.from, deposit.to, deposit.block_number, deposit.block_hash), (sender, ours, 2, block_hash));
+                (deposit.value, deposit.log_index)
+            }
+            event => panic!("unexpected {:?}", event),
+        }).collect();
+        assert_eq!(deposits, vec![(U256::from(100), Some(U256::one())), (U256::from(200), Some(U256::from(5)))]);
+
+        let params = node.params("eth_getLogs");
+        assert_eq!(params[0][0]["blockHash"], format!("{:?}", block_hash));
+        assert_eq!(params[0][0]["topics"][2], format!("{:?}", H256::from(ours)));
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_run_retry() {
+        let ours = Address::random();
+        let deposit = Transaction { hash: H256::random(), from: Some(Address::random()), to: Some(ours), value: U256::one(), ..Default::default() };
+        let mut chain = Chain::new(3, 4);
+        chain.transactions.push((2, deposit));
+        let chain = Arc::new(Mutex::new(chain));
+        // 前 3 次请求区块高度返回限流错误, 区块 1 第一次请求时返回错误
+        let failures = Arc::new(AtomicUsize::new(0));
+        let block_failed = Arc::new(AtomicUsize::new(0));
+        let node = {
+            let chain = chain.clone();
+            MockNode::start_with(default_responses(), move |method, params| {
+                if method == "eth_blockNumber" && failures.fetch_add(1, Ordering::SeqCst) < 3 {
+                    return Some(rpc_error(429, "rate limited", None));
+                }
+                if method == "eth_getBlockByNumber" && params[0] == "0x1" && block_failed.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Some(rpc_error(-32000, "upstream timeout", None));
+                }
+                let chain = chain.lock().unwrap();
+                match method {
+                    "eth_blockNumber" => Some(json!(format!("{:#x}", chain.head))),
+                    "eth_getBlockByNumber" => Some(chain.block(u64::from_str_radix(params[0].as_str()?.trim_start_matches("0x"), 16).ok()?)),
+                    _ => None,
+                }
+            }).await
+        };
+
+        let path = cursor_path();
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), &path)
+            .confirmations(0)
+            .start_block(0)
+            .poll_interval(Duration::from_millis(10));
+        watcher.add_address(ours);
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let received = events.clone();
+        let result = tokio::time::timeout(Duration::from_secs(2), watcher.run(move |event| received.lock().unwrap().push(event))).await;
+        // 一直运行到超时, 没有因为节点错误退出
+        assert!(result.is_err());
+        assert!(matches!(&events.lock().unwrap()[..], [DepositEvent::Deposit(Deposit { block_number: 2, .. })]));
+        assert_eq!(Cursor::load(&path).unwrap().unwrap().next_block, 4);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn watcher_run_fatal() {
+        let chain = Arc::new(Mutex::new(Chain::new(3, 4)));
+        let node = chain_node(chain).await;
+        // 游标目录不存在, 写入失败时停止
+        let path = std::env::temp_dir().join(format!("missing-{:x}", H256::random())).join("cursor.json");
+        let mut watcher = DepositWatcher::new(Engine::new(node.url).unwrap(), path).confirmations(0).start_block(0);
+        let result = tokio::time::timeout(Duration::from_secs(2), watcher.run(|_| {})).await;
+        assert!(matches!(result, Ok(Err(Error::ErrIo(_)))));
+    }
+}